@@ -4,6 +4,9 @@ import { useContext } from "react";
 // import js-cookie
 import Cookies from "js-cookie";
 
+// import service API
+import Api from "../../services/api";
+
 // import hook useNavigate dari react-router
 import { useNavigate } from "react-router";
 
@@ -24,8 +27,16 @@ export const useLogout = (): (() => void) => {
     // Fungsi logout
     const logout = (): void => {
 
-        // Hapus token dan user dari cookie
+        // Cabut sesi di server supaya refresh token tidak bisa dipakai lagi
+        const refreshToken = Cookies.get("refresh_token");
+
+        if (refreshToken) {
+            Api.post("/api/logout", { refresh_token: refreshToken }).catch(() => {});
+        }
+
+        // Hapus token, refresh token dan user dari cookie
         Cookies.remove("token");
+        Cookies.remove("refresh_token");
         Cookies.remove("user");
 
         // Ubah status autentikasi menjadi false
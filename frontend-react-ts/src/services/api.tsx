@@ -1,7 +1,74 @@
-import axios from "axios";
+import axios, { type AxiosError, type InternalAxiosRequestConfig } from "axios";
+
+// import js cookie
+import Cookies from "js-cookie";
 
 const Api = axios.create({
     baseURL: "http://localhost:3000",
 })
 
-export default Api
\ No newline at end of file
+// request yang sudah pernah diulang setelah refresh, supaya tidak mengulang tanpa henti
+interface RetryRequestConfig extends InternalAxiosRequestConfig {
+    _retry?: boolean;
+}
+
+// refresh yang sedang berjalan dipakai bersama, refresh token dirotasi sehingga hanya boleh dipakai sekali
+let refreshing: Promise<string> | null = null;
+
+const refreshAccessToken = async (): Promise<string> => {
+    const refreshToken = Cookies.get('refresh_token');
+
+    if (!refreshToken) {
+        throw new Error('refresh token not found');
+    }
+
+    // pakai axios biasa supaya request refresh tidak melewati interceptor ini
+    const response = await axios.post(`${Api.defaults.baseURL}/api/token/refresh`, {
+        refresh_token: refreshToken
+    });
+
+    // simpan pasangan token baru hasil rotasi
+    Cookies.set('token', response.data.data.token);
+    Cookies.set('refresh_token', response.data.data.refresh_token);
+
+    Api.defaults.headers.common['Authorization'] = `Bearer ${response.data.data.token}`;
+
+    return response.data.data.token;
+}
+
+// access token kedaluwarsa (401) → refresh sekali lalu ulangi request
+Api.interceptors.response.use(
+    (response) => response,
+    async (error: AxiosError) => {
+        const request = error.config as RetryRequestConfig | undefined;
+
+        if (error.response?.status !== 401 || !request || request._retry || request.url === '/api/login') {
+            return Promise.reject(error);
+        }
+
+        request._retry = true;
+
+        try {
+            refreshing = refreshing ?? refreshAccessToken().finally(() => {
+                refreshing = null;
+            });
+
+            const token = await refreshing;
+
+            request.headers['Authorization'] = `Bearer ${token}`;
+
+            return Api(request);
+        } catch {
+            // refresh token tidak berlaku lagi, sesi selesai
+            Cookies.remove('token');
+            Cookies.remove('refresh_token');
+            Cookies.remove('user');
+
+            window.location.href = '/login';
+
+            return Promise.reject(error);
+        }
+    }
+)
+
+export default Api
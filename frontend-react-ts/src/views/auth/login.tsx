@@ -42,8 +42,9 @@ export default function Login() {
                 password: password
             });
 
-            // set token to cookies
+            // set token and refresh token to cookies
             Cookies.set('token', response.data.data.token);
+            Cookies.set('refresh_token', response.data.data.refresh_token);
 
             // set isAuthenticated to true
             if (setIsAuthenticated) {
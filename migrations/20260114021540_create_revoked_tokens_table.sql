@@ -0,0 +1,12 @@
+-- Add migration script here
+CREATE TABLE revoked_tokens (
+    jti CHAR(32) PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_revoked_tokens_expires_at (expires_at)
+);
+
+-- token yang diterbitkan sebelum waktu ini ditolak (logout dari semua perangkat)
+ALTER TABLE users
+    ADD COLUMN tokens_revoked_before TIMESTAMP NULL DEFAULT NULL;
@@ -0,0 +1,6 @@
+-- Add migration script here
+ALTER TABLE users
+    ADD COLUMN role ENUM('admin', 'member') NOT NULL DEFAULT 'member' AFTER password;
+
+-- user pertama dijadikan admin supaya aplikasi tetap bisa dikelola
+UPDATE users SET role = 'admin' ORDER BY id ASC LIMIT 1;
@@ -0,0 +1,3 @@
+-- Add migration script here
+ALTER TABLE users
+    ADD FULLTEXT INDEX ft_users_name_email (name, email);
@@ -0,0 +1,20 @@
+-- Add migration script here
+ALTER TABLE users
+    ADD COLUMN deleted_at TIMESTAMP NULL DEFAULT NULL,
+    ADD INDEX idx_users_deleted_at (deleted_at);
+
+-- email cukup unik di antara user yang belum dihapus,
+-- user di trash tidak menghalangi email yang sama untuk didaftarkan ulang
+ALTER TABLE users
+    DROP INDEX email,
+    ADD COLUMN active_email VARCHAR(100)
+        AS (IF(deleted_at IS NULL, email, NULL)) STORED,
+    ADD UNIQUE INDEX uq_users_active_email (active_email);
+
+-- permission hapus permanen, hanya untuk admin
+INSERT INTO permissions (name, description) VALUES
+    ('users.purge', 'Menghapus user secara permanen dari trash');
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.name = 'users.purge'
+WHERE r.name = 'admin';
@@ -0,0 +1,23 @@
+-- Add migration script here
+CREATE TABLE audit_logs (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    -- tanpa foreign key supaya log tetap ada setelah user dihapus permanen
+    actor_id BIGINT NULL,
+    action VARCHAR(50) NOT NULL,
+    target_id BIGINT NOT NULL,
+    changes JSON NULL,
+    ip_address VARCHAR(45) NULL,
+    user_agent VARCHAR(255) NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_audit_logs_actor_id (actor_id),
+    INDEX idx_audit_logs_target_id (target_id),
+    INDEX idx_audit_logs_action (action),
+    INDEX idx_audit_logs_created_at (created_at)
+);
+
+INSERT INTO permissions (name, description) VALUES
+    ('audit.read', 'Melihat audit log perubahan user');
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.name = 'audit.read'
+WHERE r.name = 'admin';
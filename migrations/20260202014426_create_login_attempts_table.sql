@@ -0,0 +1,9 @@
+-- Add migration script here
+-- dipakai bila LOGIN_THROTTLE_STORE=database (lebih dari satu node)
+CREATE TABLE login_attempts (
+    -- "account:<email>" atau "ip:<alamat ip>"
+    attempt_key VARCHAR(300) PRIMARY KEY,
+    failures INT UNSIGNED NOT NULL DEFAULT 0,
+    last_failed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    locked_until TIMESTAMP NULL DEFAULT NULL
+);
@@ -0,0 +1,18 @@
+-- Add migration script here
+ALTER TABLE users
+    -- secret TOTP (base32), terisi sejak setup tapi baru aktif setelah dikonfirmasi
+    ADD COLUMN two_factor_secret VARCHAR(64) NULL,
+    ADD COLUMN two_factor_confirmed_at TIMESTAMP NULL,
+    -- step TOTP terakhir yang dipakai, mencegah kode yang sama dipakai ulang
+    ADD COLUMN two_factor_last_used_step BIGINT NULL;
+
+CREATE TABLE two_factor_recovery_codes (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    code_hash CHAR(64) NOT NULL,
+    used_at TIMESTAMP NULL DEFAULT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_two_factor_recovery_codes_user_id (user_id),
+    CONSTRAINT fk_two_factor_recovery_codes_user_id FOREIGN KEY (user_id)
+        REFERENCES users (id) ON DELETE CASCADE
+);
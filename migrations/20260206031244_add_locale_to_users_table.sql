@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE users
+    -- bahasa pesan API pilihan user (id / en), NULL berarti ikut header Accept-Language
+    ADD COLUMN locale VARCHAR(5) NULL;
@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE refresh_tokens
+    -- sesi dimulai lewat login 2FA, ikut diwariskan saat token dirotasi
+    ADD COLUMN mfa_verified BOOLEAN NOT NULL DEFAULT FALSE AFTER family_id;
@@ -0,0 +1,5 @@
+-- Add migration script here
+-- index unik email dihapus saat soft delete (lihat active_email),
+-- lookup "WHERE email = ? AND deleted_at IS NULL" di login, lupa password & cek email tetap butuh index
+ALTER TABLE users
+    ADD INDEX idx_users_email (email);
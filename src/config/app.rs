@@ -0,0 +1,646 @@
+use serde::{
+    Deserialize,
+    de::{DeserializeOwned, IntoDeserializer, value::Error as ValueError},
+};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    net::{IpAddr, Ipv4Addr},
+    path::PathBuf,
+    str::FromStr,
+    sync::Arc,
+};
+
+use crate::mail::smtp_mailer::SmtpEncryption;
+use crate::utils::problem::ErrorFormat;
+
+// panjang minimal secret HMAC supaya tidak mudah di-brute force
+const MIN_SECRET_LENGTH: usize = 32;
+
+// dipakai bila ada, CONFIG_FILE untuk memakai file lain
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+// konfigurasi aplikasi, urutan sumber: default → file TOML → environment variable
+// divalidasi sekali saat startup lalu dibagikan lewat Extension<Arc<AppConfig>>
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    pub app: AppSettings,
+    pub database: DatabaseConfig,
+    pub auth: AuthConfig,
+    pub jwt: JwtConfig,
+    pub mail: MailConfig,
+    pub log: LogConfig,
+    pub errors: ErrorConfig,
+    pub metrics: MetricsConfig,
+    // per grup route, mis. auth = "10/60"
+    pub rate_limit: BTreeMap<String, RateLimitPolicy>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppSettings {
+    // nama aplikasi, tampil di aplikasi authenticator 2FA
+    pub name: String,
+    // base URL API, dipakai untuk link verifikasi email & issuer JWT
+    pub url: String,
+    // base URL frontend, halaman reset password ada di sana
+    pub frontend_url: String,
+    // secret HMAC token internal, default jwt.secret
+    pub key: Option<String>,
+    // alamat bind, "0.0.0.0" / "::" supaya bisa diakses dari luar container
+    pub host: IpAddr,
+    pub port: u16,
+    // bila diisi server listen di unix socket ini, host & port diabaikan
+    pub socket: Option<PathBuf>,
+    // batas menunggu request yang masih berjalan setelah SIGTERM / SIGINT
+    pub shutdown_timeout_seconds: u64,
+    // X-Forwarded-For hanya dipercaya bila aplikasi berada di belakang proxy
+    pub trust_proxy: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            name: "Backend API".to_string(),
+            url: "http://localhost:3001".to_string(),
+            frontend_url: "http://localhost:5173".to_string(),
+            key: None,
+            host: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            port: 3001,
+            socket: None,
+            shutdown_timeout_seconds: 30,
+            trust_proxy: false,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub max_connections: u32,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            max_connections: 10,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoginThrottleStore {
+    Memory,
+    Database,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    pub bcrypt_cost: u32,
+    pub access_token_ttl_minutes: i64,
+    pub refresh_token_ttl_days: i64,
+    // login ditolak untuk akun yang belum verifikasi email
+    pub require_email_verification: bool,
+    // permission admin hanya berlaku untuk sesi yang login lewat 2FA
+    pub require_two_factor_for_admins: bool,
+    pub login_throttle_store: LoginThrottleStore,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            bcrypt_cost: 10,
+            access_token_ttl_minutes: 15,
+            refresh_token_ttl_days: 30,
+            require_email_verification: false,
+            require_two_factor_for_admins: true,
+            login_throttle_store: LoginThrottleStore::Memory,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum JwtAlgorithm {
+    HS256,
+    RS256,
+    EdDSA,
+}
+
+// public key lama yang masih diterima selama rotasi
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PreviousKey {
+    pub kid: String,
+    pub public_key_path: PathBuf,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JwtConfig {
+    pub algorithm: JwtAlgorithm,
+    // HS256
+    pub secret: Option<String>,
+    // RS256 / EdDSA, opsional untuk HS256
+    pub key_id: Option<String>,
+    pub private_key_path: Option<PathBuf>,
+    pub public_key_path: Option<PathBuf>,
+    pub previous_keys: Vec<PreviousKey>,
+    // default app.url
+    pub issuer: Option<String>,
+    // default issuer, token diterima bila salah satu audience cocok
+    pub audience: Vec<String>,
+    // toleransi selisih jam untuk exp/nbf/iat
+    pub leeway_seconds: u64,
+}
+
+impl Default for JwtConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: JwtAlgorithm::HS256,
+            secret: None,
+            key_id: None,
+            private_key_path: None,
+            public_key_path: None,
+            previous_keys: Vec::new(),
+            issuer: None,
+            audience: Vec::new(),
+            leeway_seconds: 60,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MailDriver {
+    Log,
+    Smtp,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SmtpConfig {
+    pub host: Option<String>,
+    pub port: u16,
+    pub encryption: SmtpEncryption,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            host: None,
+            port: 587,
+            encryption: SmtpEncryption::StartTls,
+            username: None,
+            password: None,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MailConfig {
+    pub driver: MailDriver,
+    pub from: String,
+    // driver log: email ditulis ke file ini, default stdout
+    pub log_path: Option<PathBuf>,
+    pub smtp: SmtpConfig,
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        Self {
+            driver: MailDriver::Log,
+            from: "no-reply@localhost".to_string(),
+            log_path: None,
+            smtp: SmtpConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Json,
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    pub format: LogFormat,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ErrorConfig {
+    // client tetap bisa meminta problem+json lewat header Accept
+    pub format: ErrorFormat,
+    // prefix URI field "type", mis. https://api.example.com/problems
+    pub problem_type_base_url: Option<String>,
+}
+
+impl Default for ErrorConfig {
+    fn default() -> Self {
+        Self {
+            format: ErrorFormat::Envelope,
+            problem_type_base_url: None,
+        }
+    }
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetricsConfig {
+    // bearer token untuk GET /metrics, kosong berarti endpoint /metrics tidak dipasang
+    pub token: Option<String>,
+}
+
+// "<request>/<detik>", mis. "10/60"
+#[derive(Clone, Copy, Deserialize)]
+#[serde(try_from = "String")]
+pub struct RateLimitPolicy {
+    pub capacity: u32,
+    pub window_seconds: u64,
+}
+
+impl FromStr for RateLimitPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let policy = value.split_once('/').and_then(|(capacity, window)| {
+            Some(Self {
+                capacity: capacity.trim().parse().ok()?,
+                window_seconds: window.trim().parse().ok()?,
+            })
+        });
+
+        match policy {
+            Some(policy) if policy.capacity > 0 && policy.window_seconds > 0 => Ok(policy),
+            _ => Err(format!("{value} (expected <request>/<seconds>)")),
+        }
+    }
+}
+
+impl TryFrom<String> for RateLimitPolicy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+// environment variable kosong dianggap tidak diisi
+fn var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+// menimpa nilai config dengan environment variable, error dikumpulkan supaya tampil sekaligus
+#[derive(Default)]
+struct Overrides {
+    errors: Vec<String>,
+}
+
+impl Overrides {
+    fn string(&mut self, name: &str, target: &mut String) {
+        if let Some(value) = var(name) {
+            *target = value;
+        }
+    }
+
+    fn optional<T: From<String>>(&mut self, name: &str, target: &mut Option<T>) {
+        if let Some(value) = var(name) {
+            *target = Some(T::from(value));
+        }
+    }
+
+    fn parse<T: FromStr>(&mut self, name: &str, target: &mut T)
+    where
+        T::Err: Display,
+    {
+        if let Some(value) = var(name) {
+            match value.parse() {
+                Ok(parsed) => *target = parsed,
+                Err(err) => self.errors.push(format!("Invalid {name}: {value} ({err})")),
+            }
+        }
+    }
+
+    // true/false/1/0 tanpa memperhatikan huruf besar, nilai lain ditolak supaya typo tidak diam-diam menjadi false
+    fn flag(&mut self, name: &str, target: &mut bool) {
+        if let Some(value) = var(name) {
+            match value.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" => *target = true,
+                "false" | "0" => *target = false,
+                _ => self.errors.push(format!(
+                    "Invalid {name}: {value} (expected true, false, 1 or 0)"
+                )),
+            }
+        }
+    }
+
+    // nilai enum ditulis sama seperti di file TOML, mis. MAIL_DRIVER=smtp
+    fn choice<T: DeserializeOwned>(&mut self, name: &str, target: &mut T) {
+        if let Some(value) = var(name) {
+            match T::deserialize(value.as_str().into_deserializer()) {
+                Ok(parsed) => *target = parsed,
+                Err(err) => {
+                    let err: ValueError = err;
+                    self.errors.push(format!("Invalid {name}: {err}"));
+                }
+            }
+        }
+    }
+
+    // daftar dipisah koma, mis. JWT_AUDIENCE="a,b"
+    fn list(&mut self, name: &str, target: &mut Vec<String>) {
+        if let Some(value) = var(name) {
+            *target = value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+    }
+}
+
+impl AppConfig {
+    // file TOML opsional, kecuali bila CONFIG_FILE diisi
+    fn from_file() -> Result<Self, String> {
+        let (path, required) = match var("CONFIG_FILE") {
+            Some(path) => (PathBuf::from(path), true),
+            None => (PathBuf::from(DEFAULT_CONFIG_FILE), false),
+        };
+
+        if !required && !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .map_err(|err| format!("Failed to Read Config File {}: {err}", path.display()))?;
+
+        toml::from_str(&content)
+            .map_err(|err| format!("Invalid Config File {}: {err}", path.display()))
+    }
+
+    // nama environment variable sama dengan sebelum ada file config
+    fn apply_env(&mut self) -> Vec<String> {
+        let mut env = Overrides::default();
+
+        env.string("APP_NAME", &mut self.app.name);
+        env.string("APP_URL", &mut self.app.url);
+        env.string("FRONTEND_URL", &mut self.app.frontend_url);
+        env.optional("APP_KEY", &mut self.app.key);
+        env.parse("APP_HOST", &mut self.app.host);
+        env.parse("APP_PORT", &mut self.app.port);
+        env.optional("APP_SOCKET", &mut self.app.socket);
+        env.parse(
+            "APP_SHUTDOWN_TIMEOUT_SECONDS",
+            &mut self.app.shutdown_timeout_seconds,
+        );
+        env.flag("TRUST_PROXY", &mut self.app.trust_proxy);
+
+        env.string("DATABASE_URL", &mut self.database.url);
+        env.parse(
+            "DATABASE_MAX_CONNECTIONS",
+            &mut self.database.max_connections,
+        );
+
+        env.parse("BCRYPT_COST", &mut self.auth.bcrypt_cost);
+        env.parse(
+            "ACCESS_TOKEN_TTL_MINUTES",
+            &mut self.auth.access_token_ttl_minutes,
+        );
+        env.parse(
+            "REFRESH_TOKEN_TTL_DAYS",
+            &mut self.auth.refresh_token_ttl_days,
+        );
+        env.flag(
+            "REQUIRE_EMAIL_VERIFICATION",
+            &mut self.auth.require_email_verification,
+        );
+        env.flag(
+            "REQUIRE_TWO_FACTOR_FOR_ADMINS",
+            &mut self.auth.require_two_factor_for_admins,
+        );
+        env.choice("LOGIN_THROTTLE_STORE", &mut self.auth.login_throttle_store);
+
+        env.choice("JWT_ALGORITHM", &mut self.jwt.algorithm);
+        env.optional("JWT_SECRET", &mut self.jwt.secret);
+        env.optional("JWT_KEY_ID", &mut self.jwt.key_id);
+        env.optional("JWT_PRIVATE_KEY_PATH", &mut self.jwt.private_key_path);
+        env.optional("JWT_PUBLIC_KEY_PATH", &mut self.jwt.public_key_path);
+        env.optional("JWT_ISSUER", &mut self.jwt.issuer);
+        env.list("JWT_AUDIENCE", &mut self.jwt.audience);
+        env.parse("JWT_LEEWAY_SECONDS", &mut self.jwt.leeway_seconds);
+
+        // JWT_PREVIOUS_KEYS="kid=path,kid=path", menggantikan jwt.previous_keys dari file config
+        if var("JWT_PREVIOUS_KEYS").is_some() {
+            let mut previous = Vec::new();
+            env.list("JWT_PREVIOUS_KEYS", &mut previous);
+
+            self.jwt.previous_keys.clear();
+
+            for entry in &previous {
+                match entry.split_once('=') {
+                    Some((kid, path)) => self.jwt.previous_keys.push(PreviousKey {
+                        kid: kid.trim().to_string(),
+                        public_key_path: PathBuf::from(path.trim()),
+                    }),
+                    None => env
+                        .errors
+                        .push(format!("Invalid JWT_PREVIOUS_KEYS entry: {entry}")),
+                }
+            }
+        }
+
+        env.choice("MAIL_DRIVER", &mut self.mail.driver);
+        env.string("MAIL_FROM", &mut self.mail.from);
+        env.optional("MAIL_LOG_PATH", &mut self.mail.log_path);
+        env.optional("SMTP_HOST", &mut self.mail.smtp.host);
+        env.parse("SMTP_PORT", &mut self.mail.smtp.port);
+        env.choice("SMTP_ENCRYPTION", &mut self.mail.smtp.encryption);
+        env.optional("SMTP_USERNAME", &mut self.mail.smtp.username);
+        env.optional("SMTP_PASSWORD", &mut self.mail.smtp.password);
+
+        env.choice("LOG_FORMAT", &mut self.log.format);
+
+        env.choice("ERROR_FORMAT", &mut self.errors.format);
+        env.optional(
+            "PROBLEM_TYPE_BASE_URL",
+            &mut self.errors.problem_type_base_url,
+        );
+
+        env.optional("METRICS_TOKEN", &mut self.metrics.token);
+
+        // RATE_LIMIT_<GROUP>="<request>/<detik>"
+        for (name, value) in std::env::vars() {
+            let Some(group) = name.strip_prefix("RATE_LIMIT_") else {
+                continue;
+            };
+
+            if value.is_empty() {
+                continue;
+            }
+
+            match value.parse() {
+                Ok(policy) => {
+                    self.rate_limit.insert(group.to_lowercase(), policy);
+                }
+                Err(err) => env.errors.push(format!("Invalid {name}: {err}")),
+            }
+        }
+
+        env.errors
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        let mut secret = |name: &str, value: &Option<String>| match value {
+            Some(value) if value.len() < MIN_SECRET_LENGTH => {
+                errors.push(format!(
+                    "{name} must be at least {MIN_SECRET_LENGTH} characters"
+                ));
+            }
+            Some(_) => {}
+            None => errors.push(format!("{name} must set")),
+        };
+
+        if self.jwt.algorithm == JwtAlgorithm::HS256 {
+            secret("jwt.secret (JWT_SECRET)", &self.jwt.secret);
+        }
+
+        if self.metrics.token.is_some() {
+            secret("metrics.token (METRICS_TOKEN)", &self.metrics.token);
+        }
+
+        // token internal memakai app.key, atau jwt.secret bila app.key kosong
+        match &self.app.key {
+            Some(_) => secret("app.key (APP_KEY)", &self.app.key),
+            None if self.jwt.algorithm == JwtAlgorithm::HS256 => {}
+            None if self.jwt.secret.is_some() => {
+                secret("jwt.secret (JWT_SECRET)", &self.jwt.secret)
+            }
+            None => secret("app.key (APP_KEY)", &None),
+        }
+
+        if self.jwt.algorithm != JwtAlgorithm::HS256 {
+            if self.jwt.key_id.is_none() {
+                errors.push("jwt.key_id (JWT_KEY_ID) must set".to_string());
+            }
+            if self.jwt.private_key_path.is_none() {
+                errors.push("jwt.private_key_path (JWT_PRIVATE_KEY_PATH) must set".to_string());
+            }
+            if self.jwt.public_key_path.is_none() {
+                errors.push("jwt.public_key_path (JWT_PUBLIC_KEY_PATH) must set".to_string());
+            }
+        }
+
+        let mut kids: Vec<&str> = self.jwt.key_id.iter().map(String::as_str).collect();
+
+        for previous in &self.jwt.previous_keys {
+            if kids.contains(&previous.kid.as_str()) {
+                errors.push(format!("Duplicate JWT key id: {}", previous.kid));
+            }
+            kids.push(&previous.kid);
+        }
+
+        if cfg!(not(unix)) && self.app.socket.is_some() {
+            errors.push("app.socket (APP_SOCKET) is only supported on unix".to_string());
+        }
+
+        // unix socket tidak punya alamat client, IP untuk rate limit & audit log dari X-Forwarded-For
+        if self.app.socket.is_some() && !self.app.trust_proxy {
+            errors.push(
+                "app.trust_proxy (TRUST_PROXY) must be enabled when app.socket (APP_SOCKET) is set"
+                    .to_string(),
+            );
+        }
+
+        if self.database.url.is_empty() {
+            errors.push("database.url (DATABASE_URL) must set".to_string());
+        }
+
+        if self.database.max_connections == 0 {
+            errors.push(
+                "database.max_connections (DATABASE_MAX_CONNECTIONS) must be at least 1"
+                    .to_string(),
+            );
+        }
+
+        if !(4..=31).contains(&self.auth.bcrypt_cost) {
+            errors.push("auth.bcrypt_cost (BCRYPT_COST) must be between 4 and 31".to_string());
+        }
+
+        if self.auth.access_token_ttl_minutes <= 0 {
+            errors.push(
+                "auth.access_token_ttl_minutes (ACCESS_TOKEN_TTL_MINUTES) must be positive"
+                    .to_string(),
+            );
+        }
+
+        if self.auth.refresh_token_ttl_days <= 0 {
+            errors.push(
+                "auth.refresh_token_ttl_days (REFRESH_TOKEN_TTL_DAYS) must be positive".to_string(),
+            );
+        }
+
+        if self.mail.driver == MailDriver::Smtp && self.mail.smtp.host.is_none() {
+            errors.push("mail.smtp.host (SMTP_HOST) must set".to_string());
+        }
+
+        errors
+    }
+
+    pub fn load() -> Result<Self, Vec<String>> {
+        let mut config = Self::from_file().map_err(|err| vec![err])?;
+
+        let errors = config.apply_env();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let errors = config.validate();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(config)
+    }
+
+    // token internal ditandatangani APP_KEY, JWT_SECRET tetap diterima supaya link yang sudah terkirim tetap valid
+    pub fn app_key(&self) -> &str {
+        self.app
+            .key
+            .as_deref()
+            .or(self.jwt.secret.as_deref())
+            .unwrap_or_default()
+    }
+
+    pub fn issuer(&self) -> &str {
+        self.jwt.issuer.as_deref().unwrap_or(&self.app.url)
+    }
+}
+
+// dipanggil sekali saat startup dari main, server tidak jalan bila config tidak valid
+pub fn load() -> Arc<AppConfig> {
+    match AppConfig::load() {
+        Ok(config) => Arc::new(config),
+        Err(errors) => {
+            eprintln!("Invalid configuration:");
+            for error in errors {
+                eprintln!("  - {error}");
+            }
+            std::process::exit(1);
+        }
+    }
+}
@@ -1,19 +1,25 @@
-use sqlx::mysql::{MySqlPool, MySqlPoolOptions};
+use sqlx::{
+    migrate::Migrator,
+    mysql::{MySqlPool, MySqlPoolOptions},
+};
 
-pub async fn connect() -> MySqlPool {
-    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must set");
+use crate::config::app::DatabaseConfig;
 
+// migration di folder migrations/, dipakai readiness untuk mengecek migration yang belum dijalankan
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+pub async fn connect(config: &DatabaseConfig) -> MySqlPool {
     match MySqlPoolOptions::new()
-        .max_connections(10)
-        .connect(&database_url)
+        .max_connections(config.max_connections)
+        .connect(&config.url)
         .await 
     {
         Ok(pool) => {
-            println!("Database Connected Successfully!");
+            tracing::info!("Database Connected Successfully!");
             pool
         }
         Err(err) => {
-            eprintln!("Failed to Connect to Database: {err:?}");
+            tracing::error!(error = ?err, "Failed to Connect to Database");
             std::process::exit(1);
         }
     }
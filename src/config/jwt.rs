@@ -0,0 +1,98 @@
+use jsonwebtoken::Algorithm;
+use std::path::Path;
+
+use crate::config::app::{AppConfig, JwtAlgorithm, JwtConfig};
+use crate::utils::{
+    jwt::JwtOptions,
+    jwt_keys::{JwtKeys, KeyError, VerificationKey},
+};
+
+fn read_pem(path: &Path) -> Vec<u8> {
+    match std::fs::read(path) {
+        Ok(pem) => pem,
+        Err(err) => {
+            tracing::error!(error = %err, path = %path.display(), "Failed to Read Key File");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn exit_on_error<T>(result: Result<T, KeyError>) -> T {
+    result.unwrap_or_else(|err| {
+        tracing::error!(error = %err, "Failed to Load JWT Keys");
+        std::process::exit(1);
+    })
+}
+
+// key JWT dari config, keberadaan secret / path key sudah divalidasi saat config dimuat
+//
+// HS256 (default): jwt.secret
+// RS256 | EdDSA: jwt.key_id, jwt.private_key_path, jwt.public_key_path
+// jwt.previous_keys: public key lama yang masih diterima selama rotasi
+pub fn keys(config: &JwtConfig) -> JwtKeys {
+    let algorithm = match config.algorithm {
+        JwtAlgorithm::HS256 => {
+            let secret = config.secret.as_deref().unwrap_or_default();
+
+            return JwtKeys::hmac(config.key_id.clone(), secret.as_bytes());
+        }
+        JwtAlgorithm::RS256 => Algorithm::RS256,
+        JwtAlgorithm::EdDSA => Algorithm::EdDSA,
+    };
+
+    let (Some(kid), Some(private_key_path), Some(public_key_path)) = (
+        &config.key_id,
+        &config.private_key_path,
+        &config.public_key_path,
+    ) else {
+        tracing::error!("JWT_KEY_ID, JWT_PRIVATE_KEY_PATH & JWT_PUBLIC_KEY_PATH must set");
+        std::process::exit(1);
+    };
+
+    let private_pem = read_pem(private_key_path);
+    let public_pem = read_pem(public_key_path);
+
+    let mut verification = vec![exit_on_error(VerificationKey::from_public_pem(
+        kid,
+        algorithm,
+        &public_pem,
+    ))];
+
+    for previous in &config.previous_keys {
+        verification.push(exit_on_error(VerificationKey::from_public_pem(
+            &previous.kid,
+            algorithm,
+            &read_pem(&previous.public_key_path),
+        )));
+    }
+
+    exit_on_error(JwtKeys::asymmetric(
+        algorithm,
+        kid.clone(),
+        &private_pem,
+        verification,
+    ))
+}
+
+// secret HMAC untuk token internal (verifikasi email, reset password, MFA)
+pub fn app_key(config: &AppConfig) -> Vec<u8> {
+    config.app_key().as_bytes().to_vec()
+}
+
+// iss & aud access token, default app.url supaya token dari sistem lain dengan secret yang sama ditolak
+pub fn options(config: &AppConfig) -> JwtOptions {
+    let issuer = config.issuer().to_string();
+
+    let audience = if config.jwt.audience.is_empty() {
+        vec![issuer.clone()]
+    } else {
+        config.jwt.audience.clone()
+    };
+
+    JwtOptions {
+        issuer,
+        audience,
+        leeway_seconds: config.jwt.leeway_seconds,
+        access_token_ttl_minutes: config.auth.access_token_ttl_minutes,
+    }
+}
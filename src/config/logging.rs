@@ -0,0 +1,23 @@
+use tracing_subscriber::{EnvFilter, fmt};
+
+use crate::config::app::LogFormat;
+
+// format log, dipasang paling awal supaya semua log lain ikut terformat
+//
+// RUST_LOG: level per modul, default info. RUST_LOG=info,sqlx=debug untuk mencatat setiap query
+pub fn init(format: LogFormat) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let builder = fmt().with_env_filter(filter);
+
+    match format {
+        LogFormat::Pretty => builder.pretty().init(),
+        // satu baris JSON per log, field span (request_id, user_id) ikut di setiap baris
+        LogFormat::Json => builder
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(true)
+            .init(),
+    }
+}
@@ -0,0 +1,17 @@
+use sqlx::MySqlPool;
+use std::sync::Arc;
+
+use crate::config::app::LoginThrottleStore;
+use crate::utils::login_throttle::{
+    AttemptStore, DatabaseAttemptStore, LoginThrottle, MemoryAttemptStore,
+};
+
+// pilih penyimpanan percobaan login berdasarkan auth.login_throttle_store (default: memory)
+pub fn throttle(store: LoginThrottleStore, db: &MySqlPool) -> Arc<LoginThrottle> {
+    let store: Box<dyn AttemptStore> = match store {
+        LoginThrottleStore::Memory => Box::new(MemoryAttemptStore::new()),
+        LoginThrottleStore::Database => Box::new(DatabaseAttemptStore::new(db.clone())),
+    };
+
+    Arc::new(LoginThrottle::new(store))
+}
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::config::app::{MailConfig, MailDriver};
+use crate::mail::{log_mailer::LogMailer, mailer::MailSender, smtp_mailer::SmtpMailer};
+
+// pilih pengirim email berdasarkan mail.driver (default: log)
+pub fn sender(config: &MailConfig) -> Arc<dyn MailSender> {
+    match config.driver {
+        MailDriver::Smtp => {
+            let smtp = &config.smtp;
+            let host = smtp.host.as_deref().unwrap_or_default();
+
+            let credentials = smtp.username.clone().zip(smtp.password.clone());
+
+            match SmtpMailer::new(host, smtp.port, smtp.encryption, credentials, &config.from) {
+                Ok(mailer) => Arc::new(mailer),
+                Err(err) => {
+                    tracing::error!(error = %err, "Failed to Configure SMTP Mailer");
+                    std::process::exit(1);
+                }
+            }
+        }
+        MailDriver::Log => Arc::new(LogMailer::new(config.log_path.clone())),
+    }
+}
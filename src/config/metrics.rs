@@ -0,0 +1,57 @@
+use metrics::{describe_counter, describe_gauge, describe_histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::time::Duration;
+
+use crate::utils::metrics::{
+    BCRYPT_DURATION_SECONDS, DB_POOL_IDLE, DB_POOL_PROBE_DURATION_SECONDS, DB_POOL_SIZE,
+    HTTP_REQUEST_DURATION_SECONDS, HTTP_REQUESTS_TOTAL, LOGIN_ATTEMPTS_TOTAL,
+};
+
+// bucket histogram (detik), sama dengan default client Prometheus
+const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+// interval membersihkan data histogram yang sudah di-render
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+// pasang recorder global metrics, hasilnya di-render oleh handler /metrics
+pub fn recorder() -> PrometheusHandle {
+    let handle = match PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), DURATION_BUCKETS)
+        .and_then(|builder| builder.install_recorder())
+    {
+        Ok(handle) => handle,
+        Err(err) => {
+            tracing::error!(error = %err, "Failed to Install Metrics Recorder");
+            std::process::exit(1);
+        }
+    };
+
+    describe_counter!(
+        HTTP_REQUESTS_TOTAL,
+        "Jumlah request HTTP per route & status"
+    );
+    describe_histogram!(
+        HTTP_REQUEST_DURATION_SECONDS,
+        "Latency request HTTP per route"
+    );
+    describe_counter!(LOGIN_ATTEMPTS_TOTAL, "Jumlah percobaan login per hasil");
+    describe_histogram!(BCRYPT_DURATION_SECONDS, "Durasi hash & verifikasi bcrypt");
+    describe_gauge!(DB_POOL_SIZE, "Jumlah koneksi di pool MySQL");
+    describe_gauge!(DB_POOL_IDLE, "Jumlah koneksi idle di pool MySQL");
+    describe_histogram!(
+        DB_POOL_PROBE_DURATION_SECONDS,
+        "Waktu tunggu probe yang mengambil satu koneksi dari pool MySQL saat scrape, bukan waktu tunggu acquire request biasa, dibatasi 1 detik"
+    );
+
+    let upkeep = handle.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(UPKEEP_INTERVAL).await;
+            upkeep.run_upkeep();
+        }
+    });
+
+    handle
+}
@@ -1,2 +1,9 @@
+pub mod app;
 pub mod database;
-
+pub mod jwt;
+pub mod logging;
+pub mod login_throttle;
+pub mod mail;
+pub mod metrics;
+pub mod problem;
+pub mod rate_limit;
\ No newline at end of file
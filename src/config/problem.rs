@@ -0,0 +1,12 @@
+use std::sync::Arc;
+
+use crate::config::app::ErrorConfig;
+use crate::utils::problem::ProblemOptions;
+
+// format error API, client tetap bisa meminta problem+json lewat header Accept
+pub fn options(config: &ErrorConfig) -> Arc<ProblemOptions> {
+    Arc::new(ProblemOptions {
+        default_format: config.format,
+        type_base_url: config.problem_type_base_url.clone(),
+    })
+}
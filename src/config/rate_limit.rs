@@ -0,0 +1,33 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::config::app::{AppConfig, RateLimitPolicy};
+use crate::utils::rate_limit::RateLimiter;
+
+// batas default per grup route
+fn default_policy(group: &str) -> RateLimitPolicy {
+    let (capacity, window_seconds) = match group {
+        // register & login paling rawan disalahgunakan
+        "auth" => (10, 60),
+        "users" => (120, 60),
+        _ => (60, 60),
+    };
+
+    RateLimitPolicy {
+        capacity,
+        window_seconds,
+    }
+}
+
+// rate limiter untuk satu grup route, bisa diubah lewat [rate_limit] atau RATE_LIMIT_<GROUP>="<request>/<detik>"
+pub fn limiter(config: &AppConfig, group: &str) -> Arc<RateLimiter> {
+    let policy = config
+        .rate_limit
+        .get(group)
+        .copied()
+        .unwrap_or_else(|| default_policy(group));
+
+    Arc::new(RateLimiter::new(
+        policy.capacity,
+        Duration::from_secs(policy.window_seconds),
+    ))
+}
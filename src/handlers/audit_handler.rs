@@ -0,0 +1,138 @@
+use axum::{
+    Extension, Json,
+    extract::{Query, rejection::QueryRejection},
+    http::StatusCode,
+};
+use serde_json::json;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+use validator::Validate;
+
+// import model audit log
+use crate::models::audit_log::AuditLog;
+
+// import penerjemah pesan API
+use crate::i18n::messages::t;
+
+// import util error, pagination dan response API
+use crate::utils::{
+    app_error::{ApiResult, AppError},
+    pagination::{DEFAULT_PER_PAGE, PaginationLinks, PaginationMeta, last_page},
+    response::ApiResponse,
+};
+
+// import schema query audit log
+use crate::schemas::audit_log_schema::AuditLogQuery;
+
+// tambahkan filter actor, target, action & rentang tanggal ke query
+fn push_filters(builder: &mut QueryBuilder<'_, MySql>, query: &AuditLogQuery) {
+    if let Some(actor_id) = query.actor_id {
+        builder.push(" AND actor_id = ").push_bind(actor_id);
+    }
+
+    if let Some(target_id) = query.target_id {
+        builder.push(" AND target_id = ").push_bind(target_id);
+    }
+
+    if let Some(action) = query.action.as_deref().filter(|v| !v.is_empty()) {
+        builder.push(" AND action = ").push_bind(action.to_string());
+    }
+
+    if let Some(from) = query.from {
+        builder.push(" AND created_at >= ").push_bind(from);
+    }
+
+    if let Some(to) = query.to {
+        // sampai akhir hari `to`
+        builder
+            .push(" AND created_at < DATE_ADD(")
+            .push_bind(to)
+            .push(", INTERVAL 1 DAY)");
+    }
+}
+
+// link halaman lain dengan filter yang sama
+fn page_link(query: &AuditLogQuery, page: u32) -> String {
+    let query = AuditLogQuery {
+        page: Some(page),
+        ..query.clone()
+    };
+
+    format!(
+        "/api/audit-logs?{}",
+        serde_urlencoded::to_string(&query).unwrap_or_default()
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/audit-logs",
+    tag = "audit",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "List audit log", body = ApiResponse<Vec<AuditLog>>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn index(
+    Extension(db): Extension<MySqlPool>,
+    query: Result<Query<AuditLogQuery>, QueryRejection>,
+) -> ApiResult {
+    let Ok(Query(query)) = query else {
+        return Err(AppError::BadRequest("error.invalid_query"));
+    };
+
+    // Validasi Request
+    query.validate()?;
+
+    let per_page = query.per_page.unwrap_or(DEFAULT_PER_PAGE);
+    let page = query.page.unwrap_or(1);
+
+    // Hitung total audit log sesuai filter
+    let mut count = QueryBuilder::<MySql>::new("SELECT COUNT(*) FROM audit_logs WHERE 1 = 1");
+    push_filters(&mut count, &query);
+
+    let total: i64 = count.build_query_scalar().fetch_one(&db).await?;
+
+    // Ambil audit log terbaru lebih dulu
+    let mut select = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT id, actor_id, action, target_id, changes, ip_address, user_agent, created_at
+        FROM audit_logs
+        WHERE 1 = 1
+        "#,
+    );
+    push_filters(&mut select, &query);
+    select
+        .push(" ORDER BY id DESC LIMIT ")
+        .push_bind(per_page as i64)
+        .push(" OFFSET ")
+        .push_bind((page as i64 - 1) * per_page as i64);
+
+    let logs: Vec<AuditLog> = select.build_query_as().fetch_all(&db).await?;
+
+    let last = last_page(total, per_page);
+    let meta = PaginationMeta {
+        total,
+        per_page,
+        current_page: Some(page),
+        last_page: Some(last),
+        next_cursor: None,
+        links: PaginationLinks {
+            first: page_link(&query, 1),
+            prev: (page > 1).then(|| page_link(&query, page - 1)),
+            next: (page < last).then(|| page_link(&query, page + 1)),
+            last: Some(page_link(&query, last)),
+        },
+    };
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::paginated(&t("audit.list"), json!(logs), meta)),
+    ))
+}
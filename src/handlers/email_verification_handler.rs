@@ -0,0 +1,183 @@
+use axum::{
+    Extension, Json,
+    extract::{Query, rejection::QueryRejection},
+    http::StatusCode,
+};
+use chrono::Utc;
+use serde_json::json;
+use sqlx::MySqlPool;
+use std::sync::Arc;
+
+// import konfigurasi aplikasi
+use crate::config::app::AppConfig;
+
+// import pengirim dan template email
+use crate::mail::{
+    mailer::{MailSender, send_in_background},
+    templates::verification_mail,
+};
+
+// import schema request verifikasi email
+use crate::schemas::email_verification_schema::{ResendVerificationRequest, VerifyEmailQuery};
+
+// import penerjemah pesan API
+use crate::i18n::messages::t;
+
+// import util error, audit, token verifikasi dan response API
+use crate::utils::{
+    app_error::{ApiResult, AppError},
+    audit::{AuditEntry, USER_EMAIL_VERIFIED, record as record_audit},
+    client_info::ClientInfo,
+    email_verification::{
+        EMAIL_VERIFICATION_PURPOSE, issue_verification_token, revoke_verification_tokens,
+    },
+    response::ApiResponse,
+    signed_token,
+    validated_json::ValidatedJson,
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/verify-email",
+    tag = "auth",
+    params(VerifyEmailQuery),
+    responses(
+        (status = 200, description = "Email berhasil diverifikasi"),
+        (status = 400, description = "Request tidak valid"),
+        (status = 429, description = "Terlalu banyak request"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn verify(
+    Extension(db): Extension<MySqlPool>,
+    client: ClientInfo,
+    query: Result<Query<VerifyEmailQuery>, QueryRejection>,
+) -> ApiResult {
+    let Ok(Query(query)) = query else {
+        return Err(AppError::BadRequest("error.invalid_query"));
+    };
+
+    let invalid = || AppError::BadRequest("email_verification.token_invalid");
+
+    // token dengan signature yang salah langsung ditolak tanpa query ke database
+    let token_hash =
+        signed_token::verify(EMAIL_VERIFICATION_PURPOSE, &query.token).ok_or_else(invalid)?;
+
+    let mut tx = db.begin().await?;
+
+    // token dikunci supaya hanya bisa dipakai satu kali
+    let stored = sqlx::query!(
+        r#"
+        SELECT id, user_id, expires_at, used_at
+        FROM email_verification_tokens
+        WHERE token_hash = ?
+        FOR UPDATE
+        "#,
+        token_hash
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .filter(|stored| stored.used_at.is_none())
+    .ok_or_else(invalid)?;
+
+    if stored.expires_at <= Utc::now() {
+        return Err(AppError::BadRequest("email_verification.token_expired"));
+    }
+
+    sqlx::query!(
+        "UPDATE email_verification_tokens SET used_at = NOW() WHERE id = ?",
+        stored.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let updated = sqlx::query!(
+        r#"
+        UPDATE users
+        SET email_verified_at = NOW()
+        WHERE id = ? AND email_verified_at IS NULL AND deleted_at IS NULL
+        "#,
+        stored.user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if updated.rows_affected() > 0 {
+        let audit = AuditEntry {
+            actor_id: Some(stored.user_id),
+            action: USER_EMAIL_VERIFIED,
+            target_id: stored.user_id,
+            changes: json!({}),
+            client: &client,
+        };
+        record_audit(&mut *tx, audit).await?;
+    }
+
+    tx.commit().await?;
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("email_verification.verified"),
+            json!(null),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/verify-email/resend",
+    tag = "auth",
+    request_body = ResendVerificationRequest,
+    responses(
+        (status = 200, description = "Link verifikasi dikirim bila email terdaftar"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn resend(
+    Extension(db): Extension<MySqlPool>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(mailer): Extension<Arc<dyn MailSender>>,
+    ValidatedJson(payload): ValidatedJson<ResendVerificationRequest>,
+) -> ApiResult {
+    // hanya akun aktif yang belum terverifikasi yang dikirimi link baru
+    let user = sqlx::query!(
+        r#"
+        SELECT id, name, email
+        FROM users
+        WHERE email = ? AND deleted_at IS NULL AND email_verified_at IS NULL
+        "#,
+        payload.email
+    )
+    .fetch_optional(&db)
+    .await?;
+
+    if let Some(user) = user {
+        let mut tx = db.begin().await?;
+
+        // link lama tidak berlaku lagi setelah link baru dikirim
+        revoke_verification_tokens(&mut *tx, user.id).await?;
+
+        let token = issue_verification_token(&mut *tx, user.id).await?;
+
+        tx.commit().await?;
+
+        send_in_background(
+            mailer,
+            verification_mail(&config, &user.name, &user.email, &token),
+        );
+    }
+
+    // response sama untuk email terdaftar maupun tidak, supaya email tidak bisa ditebak
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("email_verification.sent"),
+            json!(null),
+        )),
+    ))
+}
@@ -0,0 +1,141 @@
+use axum::{Extension, Json, http::StatusCode};
+use sqlx::MySqlPool;
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+// import daftar migration
+use crate::config::database::MIGRATOR;
+
+// import schema response health
+use crate::schemas::health_schema::{
+    ComponentHealth, HealthStatus, LivenessResponse, ReadinessResponse,
+};
+
+// batas waktu setiap pengecekan, supaya probe tidak ikut menggantung saat database lambat
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+// tabel riwayat migration milik sqlx belum ada
+const TABLE_NOT_FOUND: &str = "42S02";
+
+fn down(error: &str) -> ComponentHealth {
+    ComponentHealth {
+        status: HealthStatus::Down,
+        required: true,
+        latency_ms: None,
+        pending: None,
+        error: Some(error.to_string()),
+    }
+}
+
+async fn check_database(db: &MySqlPool) -> ComponentHealth {
+    let started = Instant::now();
+
+    match tokio::time::timeout(CHECK_TIMEOUT, sqlx::query("SELECT 1").execute(db)).await {
+        Ok(Ok(_)) => ComponentHealth {
+            status: HealthStatus::Up,
+            required: true,
+            latency_ms: Some(started.elapsed().as_millis() as u64),
+            pending: None,
+            error: None,
+        },
+        Ok(Err(e)) => {
+            tracing::warn!(error = %e, "Database health check failed");
+            down("unavailable")
+        }
+        Err(_) => down("timeout"),
+    }
+}
+
+async fn check_migrations(db: &MySqlPool) -> ComponentHealth {
+    let applied =
+        sqlx::query_scalar::<_, i64>("SELECT version FROM _sqlx_migrations WHERE success = TRUE")
+            .fetch_all(db);
+
+    let applied = match tokio::time::timeout(CHECK_TIMEOUT, applied).await {
+        Ok(Ok(versions)) => versions,
+        // belum pernah migrate, semua migration dianggap pending
+        Ok(Err(e))
+            if e.as_database_error()
+                .and_then(|db| db.code())
+                .is_some_and(|code| code == TABLE_NOT_FOUND) =>
+        {
+            Vec::new()
+        }
+        Ok(Err(e)) => {
+            tracing::warn!(error = %e, "Migration health check failed");
+            return down("unavailable");
+        }
+        Err(_) => return down("timeout"),
+    };
+
+    let pending = MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration() && !applied.contains(&m.version))
+        .count();
+
+    ComponentHealth {
+        status: if pending == 0 {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        },
+        required: true,
+        latency_ms: None,
+        pending: Some(pending),
+        error: None,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    responses(
+        (status = 200, description = "Proses server berjalan", body = LivenessResponse),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn live() -> Json<LivenessResponse> {
+    // tidak mengecek dependency, cukup membuktikan server masih melayani request
+    Json(LivenessResponse {
+        status: HealthStatus::Up,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Semua dependency siap", body = ReadinessResponse),
+        (status = 503, description = "Ada dependency yang down", body = ReadinessResponse),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn ready(Extension(db): Extension<MySqlPool>) -> (StatusCode, Json<ReadinessResponse>) {
+    let (database, migrations) = tokio::join!(check_database(&db), check_migrations(&db));
+
+    let mut checks = BTreeMap::new();
+    checks.insert("database".to_string(), database);
+    checks.insert("migrations".to_string(), migrations);
+
+    let ready = checks
+        .values()
+        .all(|check| !check.required || check.status == HealthStatus::Up);
+
+    let (status, health) = if ready {
+        (StatusCode::OK, HealthStatus::Up)
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, HealthStatus::Down)
+    };
+
+    (
+        status,
+        Json(ReadinessResponse {
+            status: health,
+            checks,
+        }),
+    )
+}
@@ -0,0 +1,21 @@
+use axum::{
+    Json,
+    http::header::{CACHE_CONTROL, HeaderValue},
+    response::IntoResponse,
+};
+
+// import key JWT yang sedang di-load
+use crate::utils::jwt_keys::keys;
+
+// public key untuk verifikasi access token oleh service lain (format JWKS standar, tanpa ApiResponse)
+#[tracing::instrument(skip_all)]
+pub async fn jwks() -> impl IntoResponse {
+    (
+        // boleh di-cache sebentar, key baru harus sudah dipublikasikan sebelum dipakai menandatangani
+        [(
+            CACHE_CONTROL,
+            HeaderValue::from_static("public, max-age=300"),
+        )],
+        Json(keys().jwks()),
+    )
+}
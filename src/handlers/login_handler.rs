@@ -4,140 +4,197 @@ use axum::{
     http::StatusCode,
 };
 use sqlx::MySqlPool;
-use bcrypt::verify;
-use validator::Validate;
-use std::collections::HashMap;
-use serde_json::{json, Value};
+use std::sync::Arc;
+use serde_json::json;
+
+// import konfigurasi aplikasi
+use crate::config::app::AppConfig;
 
 // import schema request dan response login
 use crate::schemas::login_schema::{
     LoginRequest,
     LoginResponse,
+    MfaPendingResponse,
     UserResponse,
 };
 
-// import util jwt generate token dan response API
+// import penerjemah pesan API
+use crate::i18n::messages::t;
+
+// import util error, jwt generate token, refresh token, verifikasi email, pembatas login dan response API
 use crate::utils::{
-    jwt::generate_token,
+    app_error::{ApiResult, AppError},
+    client_info::ClientInfo,
+    jwt::{UserClaims, generate_mfa_token, generate_token},
+    login_throttle::LoginThrottle,
+    metrics::{
+        LOGIN_FAILURE, LOGIN_MFA_REQUIRED, LOGIN_PASSWORD, LOGIN_SUCCESS, LOGIN_THROTTLED,
+        LOGIN_UNVERIFIED, record_login,
+    },
+    password::verify_password,
+    permission::{load_permissions, load_role_names},
+    refresh_token::{issue_refresh_token, new_family_id},
     response::ApiResponse,
+    validated_json::ValidatedJson,
 };
 
+// response 429 Too Many Requests dengan header Retry-After
+pub fn too_many_attempts(retry_after: u64) -> AppError {
+    AppError::TooManyRequests {
+        key: "auth.too_many_login_attempts",
+        retry_after,
+    }
+}
+
+// catat login gagal untuk akun & IP, response sama untuk email terdaftar maupun tidak
+// kegagalan yang membuat akun/IP terkunci langsung dijawab 429 dengan Retry-After
+async fn failed_login(throttle: &LoginThrottle, email: &str, ip: Option<&str>) -> AppError {
+    record_login(LOGIN_PASSWORD, LOGIN_FAILURE);
+
+    match throttle.record_failure(email, ip).await {
+        Ok(Some(retry_after)) => return too_many_attempts(retry_after),
+        Ok(None) => {}
+        Err(e) => tracing::error!(error = %e, "Database error"),
+    }
+
+    AppError::Unauthorized("auth.invalid_credentials")
+}
+
+// terbitkan access token & refresh token, dipakai login biasa maupun setelah verifikasi 2FA
+// mfa: true bila login lewat verifikasi 2FA
+pub async fn complete_login(db: &MySqlPool, config: &AppConfig, user_id: i64, name: String, email: String, mfa: bool) -> ApiResult {
+    // ambil nama role milik user
+    let roles = load_role_names(db, user_id).await?;
+
+    // akun dengan permission admin yang login tanpa 2FA diminta mengaktifkan 2FA
+    let mfa_setup_required = config.auth.require_two_factor_for_admins
+        && !mfa
+        && !load_permissions(db, user_id).await?.is_empty();
+
+    // nama, email & role ikut di access token
+    let user_claims = UserClaims {
+        name: name.clone(),
+        email: email.clone(),
+        roles: roles.clone(),
+        mfa,
+    };
+
+    // generate token JWT
+    let token = generate_token(user_id, user_claims)?;
+
+    // simpan refresh token dengan family baru
+    let (_, refresh_token) = issue_refresh_token(
+        db,
+        user_id,
+        &new_family_id(),
+        mfa,
+        config.auth.refresh_token_ttl_days,
+    )
+    .await?;
+
+    let response = LoginResponse {
+        user: UserResponse {
+            id: user_id,
+            name,
+            email,
+            roles,
+        },
+        token,
+        refresh_token,
+        mfa_setup_required,
+    };
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("auth.login_success"),
+            json!(response),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Login berhasil, atau mfa_token bila akun memakai 2FA", body = ApiResponse<LoginResponse>),
+        (status = 401, description = "Email atau password salah"),
+        (status = 403, description = "Email belum diverifikasi"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request atau percobaan login, lihat Retry-After"),
+    )
+)]
+#[tracing::instrument(skip_all)]
 pub async fn login(
     Extension(db): Extension<MySqlPool>,
-    Json(payload): Json<LoginRequest>,
-) -> (StatusCode, Json<ApiResponse<Value>>) {
-
-    // Validasi Request
-    if let Err(errors) = payload.validate() {
-        let mut field_errors: HashMap<String, Vec<String>> = HashMap::new();
-
-        // kumpulkan semua error dari validasi
-        for (field, errors) in errors.field_errors() {
-            let messages = errors
-                .iter()
-                .filter_map(|e| e.message.as_ref())
-                .map(|m| m.to_string())
-                .collect::<Vec<String>>();
-
-            field_errors.insert(field.to_string(), messages);
-        }
-
-        return (
-
-            // kirim response 422 Unprocessable Entity
-            StatusCode::UNPROCESSABLE_ENTITY,
-            Json(ApiResponse {
-                status: false,
-                message: "Validasi Gagal".to_string(),
-                data: Some(json!(field_errors)),
-            }),
-        );
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(throttle): Extension<Arc<LoginThrottle>>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<LoginRequest>,
+) -> ApiResult {
+
+    // tolak lebih dulu bila akun atau IP sedang dikunci, sebelum bcrypt dijalankan
+    if let Some(retry_after) = throttle.retry_after(&payload.email, client.ip.as_deref()).await? {
+        record_login(LOGIN_PASSWORD, LOGIN_THROTTLED);
+        return Err(too_many_attempts(retry_after));
     }
 
     // get user berdasarkan email
-    let user = match sqlx::query!(
-        "SELECT id, name, email, password FROM users WHERE email = ?",
+    let user = sqlx::query!(
+        r#"
+        SELECT id, name, email, email_verified_at, password,
+            two_factor_confirmed_at IS NOT NULL AS `two_factor_enabled!: bool`
+        FROM users
+        WHERE email = ? AND deleted_at IS NULL
+        "#,
         payload.email
     )
-    .fetch_one(&db)
-    .await
-    {
-        Ok(user) => user,
-        Err(sqlx::Error::RowNotFound) => {
-            return (
-
-                // kirim response 401 Unauthorized
-                StatusCode::UNAUTHORIZED,
-                Json(ApiResponse::error(
-                    "Email atau Password Anda Salah",
-                )),
-            );
-        }
-        Err(e) => {
-            eprintln!("Database error: {}", e);
-            return (
-
-                // kirim response 500 Internal Server Error
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(
-                    "Terjadi kesalahan sistem",
-                )),
-            );
-        }
+    .fetch_optional(&db)
+    .await?;
+
+    let Some(user) = user else {
+        return Err(failed_login(&throttle, &payload.email, client.ip.as_deref()).await);
     };
 
     // Verifikasi Password Dengan Bcrypt
-    match verify(payload.password, &user.password) {
-        Ok(true) => {
-            
-            // generate token JWT
-            match generate_token(user.id) {
-                Ok(token) => {
-                    let response = LoginResponse {
-                        user: UserResponse {
-                            id: user.id,
-                            name: user.name,
-                            email: user.email,
-                        },
-                        token,
-                    };
-
-                    (
-                        // kirim response 200 OK
-                        StatusCode::OK,
-                        Json(ApiResponse::success(
-                            "Login Berhasil",
-                            json!(response),
-                        )),
-                    )
-                }
-                Err(e) => {
-                    eprintln!("JWT generation error: {:?}", e);
-                    (
-                        // kirim response 500 Internal Server Error
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(ApiResponse::error(
-                            "Gagal membuat token",
-                        )),
-                    )
-                }
-            }
-        }
-        Ok(false) => (
-
-            // kirim response 401 Unauthorized
-            StatusCode::UNAUTHORIZED,
-            Json(ApiResponse::error(
-                "Email atau Password Anda Salah",
-            )),
-        ),
-        Err(_) => (
+    if !verify_password(&payload.password, &user.password)? {
+        return Err(failed_login(&throttle, &payload.email, client.ip.as_deref()).await);
+    }
 
-            // kirim response 500 Internal Server Error
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(
-                "Gagal memverifikasi password",
+    // password benar, hitungan gagal akun direset
+    if let Err(e) = throttle.record_success(&payload.email).await {
+        tracing::error!(error = %e, "Database error");
+    }
+
+    // tolak akun yang email-nya belum diverifikasi
+    if config.auth.require_email_verification && user.email_verified_at.is_none() {
+        record_login(LOGIN_PASSWORD, LOGIN_UNVERIFIED);
+        return Err(AppError::Forbidden("auth.email_not_verified"));
+    }
+
+    // akun dengan 2FA aktif harus memasukkan kode lebih dulu di /api/login/2fa
+    if user.two_factor_enabled {
+        record_login(LOGIN_PASSWORD, LOGIN_MFA_REQUIRED);
+
+        let response = MfaPendingResponse {
+            mfa_required: true,
+            mfa_token: generate_mfa_token(user.id)?,
+        };
+
+        return Ok((
+            // kirim response 200 OK
+            StatusCode::OK,
+            Json(ApiResponse::success(
+                &t("auth.two_factor_required"),
+                json!(response),
             )),
-        ),
+        ));
     }
+
+    record_login(LOGIN_PASSWORD, LOGIN_SUCCESS);
+
+    complete_login(&db, &config, user.id, user.name, user.email, false).await
 }
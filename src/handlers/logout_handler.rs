@@ -0,0 +1,133 @@
+use axum::{
+    Extension,
+    Json,
+    http::StatusCode,
+};
+use sqlx::MySqlPool;
+use std::sync::Arc;
+use serde_json::json;
+
+// import schema request logout
+use crate::schemas::logout_schema::LogoutRequest;
+
+// import penerjemah pesan API
+use crate::i18n::messages::t;
+
+// import util error, jwt, refresh token, revocation dan response API
+use crate::utils::{
+    app_error::ApiResult,
+    jwt::Claims,
+    refresh_token::hash_token,
+    response::ApiResponse,
+    revocation::RevocationCache,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/logout",
+    tag = "auth",
+    request_body = Option<LogoutRequest>,
+    responses(
+        (status = 200, description = "Logout berhasil"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn logout(
+    Extension(db): Extension<MySqlPool>,
+    Extension(revocations): Extension<Arc<RevocationCache>>,
+    Extension(claims): Extension<Claims>,
+    payload: Option<Json<LogoutRequest>>,
+) -> ApiResult {
+
+    // cabut access token yang sedang dipakai
+    revocations.revoke(&db, &claims).await?;
+
+    // cabut seluruh family refresh token milik sesi ini
+    if let Some(Json(LogoutRequest { refresh_token: Some(refresh_token) })) = payload {
+        let family = sqlx::query!(
+            "SELECT family_id FROM refresh_tokens WHERE token_hash = ? AND user_id = ?",
+            hash_token(&refresh_token),
+            claims.sub
+        )
+        .fetch_optional(&db)
+        .await?;
+
+        if let Some(family) = family {
+            sqlx::query!(
+                r#"
+                UPDATE refresh_tokens
+                SET revoked_at = NOW()
+                WHERE family_id = ? AND revoked_at IS NULL
+                "#,
+                family.family_id
+            )
+            .execute(&db)
+            .await?;
+        }
+    }
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("auth.logout_success"),
+            json!(null),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/logout/all",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Logout dari semua perangkat berhasil"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn logout_all(
+    Extension(db): Extension<MySqlPool>,
+    Extension(revocations): Extension<Arc<RevocationCache>>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult {
+    let mut tx = db.begin().await?;
+
+    // semua access token yang terbit sebelum saat ini ditolak
+    sqlx::query!(
+        "UPDATE users SET tokens_revoked_before = NOW() WHERE id = ?",
+        claims.sub
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // cabut semua refresh token milik user
+    sqlx::query!(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = NOW()
+        WHERE user_id = ? AND revoked_at IS NULL
+        "#,
+        claims.sub
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    revocations.forget_user(claims.sub);
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("auth.logout_all_success"),
+            json!(null),
+        )),
+    ))
+}
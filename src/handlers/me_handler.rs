@@ -0,0 +1,344 @@
+use axum::{Extension, Json, http::StatusCode};
+use serde_json::json;
+use sqlx::MySqlPool;
+use std::sync::Arc;
+
+// import konfigurasi aplikasi
+use crate::config::app::AppConfig;
+
+// import pengirim dan template email
+use crate::mail::{
+    mailer::{MailSender, send_in_background},
+    templates::verification_mail,
+};
+
+// import schema request dan response profil
+use crate::schemas::me_schema::{ChangePasswordRequest, MeResponse, MeUpdateRequest};
+
+// import bahasa & penerjemah pesan API
+use crate::i18n::{locale::Locale, messages::t};
+
+// import util error, audit, jwt, permission, revocation dan response API
+use crate::utils::{
+    app_error::{ApiResult, AppError},
+    audit::{AuditEntry, USER_PASSWORD_CHANGED, USER_UPDATED, diff, record as record_audit},
+    client_info::ClientInfo,
+    email_verification::{issue_verification_token, revoke_verification_tokens},
+    jwt::Claims,
+    password::{hash_password, verify_password},
+    permission::{PermissionSet, load_role_names},
+    response::ApiResponse,
+    revocation::RevocationCache,
+    validated_json::ValidatedJson,
+};
+
+fn user_not_found() -> AppError {
+    AppError::NotFound("user.not_found")
+}
+
+// profil lengkap user yang sedang login, 404 bila user sudah dihapus
+async fn load_me(
+    db: &MySqlPool,
+    user_id: i64,
+    permissions: &PermissionSet,
+) -> Result<MeResponse, AppError> {
+    let user = sqlx::query!(
+        r#"
+        SELECT id, name, email, email_verified_at, locale, created_at, updated_at,
+            two_factor_confirmed_at IS NOT NULL AS `two_factor_enabled!: bool`
+        FROM users
+        WHERE id = ? AND deleted_at IS NULL
+        "#,
+        user_id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(user_not_found)?;
+
+    let roles = load_role_names(db, user.id).await?;
+
+    Ok(MeResponse {
+        id: user.id,
+        name: user.name,
+        email: user.email,
+        email_verified_at: user.email_verified_at,
+        two_factor_enabled: user.two_factor_enabled,
+        roles,
+        permissions: permissions.names(),
+        locale: user.locale,
+        created_at: user.created_at,
+        updated_at: user.updated_at,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/me",
+    tag = "me",
+    responses(
+        (status = 200, description = "Profil user yang sedang login", body = ApiResponse<MeResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn show(
+    Extension(db): Extension<MySqlPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(permissions): Extension<PermissionSet>,
+) -> ApiResult {
+    let me = load_me(&db, claims.sub, &permissions).await?;
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("me.profile"), json!(me))),
+    ))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/me",
+    tag = "me",
+    request_body = MeUpdateRequest,
+    responses(
+        (status = 200, description = "Profil berhasil diperbarui", body = ApiResponse<MeResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 409, description = "Email sudah terdaftar"),
+        (status = 422, description = "Validasi gagal atau password saat ini salah"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn update(
+    Extension(db): Extension<MySqlPool>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(mailer): Extension<Arc<dyn MailSender>>,
+    Extension(revocations): Extension<Arc<RevocationCache>>,
+    Extension(claims): Extension<Claims>,
+    Extension(permissions): Extension<PermissionSet>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<MeUpdateRequest>,
+) -> ApiResult {
+    let mut tx = db.begin().await?;
+
+    // data sebelum diubah, dikunci sampai transaksi selesai
+    let current = sqlx::query!(
+        "SELECT id, name, email, password, locale FROM users WHERE id = ? AND deleted_at IS NULL FOR UPDATE",
+        claims.sub
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(user_not_found)?;
+
+    let name = payload.name.unwrap_or_else(|| current.name.clone());
+    let email = payload.email.unwrap_or_else(|| current.email.clone());
+    let email_changed = email != current.email;
+
+    // simpan kode bahasa yang sudah dinormalisasi, mis. "en-US" → "en"
+    let locale = match payload.locale.as_deref().and_then(Locale::parse) {
+        Some(locale) => Some(locale.code().to_string()),
+        None => current.locale.clone(),
+    };
+
+    // ganti email wajib konfirmasi password saat ini, supaya token yang dicuri tidak cukup
+    // untuk mengambil alih akun lewat reset password ke email baru
+    if email_changed {
+        let Some(current_password) = payload
+            .current_password
+            .as_deref()
+            .filter(|p| !p.is_empty())
+        else {
+            return Err(AppError::invalid_field(
+                "current_password",
+                &t("validation.current_password_required"),
+            ));
+        };
+
+        // Verifikasi password saat ini Dengan Bcrypt
+        if !verify_password(current_password, &current.password)? {
+            return Err(AppError::invalid_field(
+                "current_password",
+                &t("password.current_incorrect"),
+            ));
+        }
+    }
+
+    // Cek email unique (kecuali diri sendiri)
+    if email_changed {
+        let email_exists = sqlx::query!(
+            "SELECT id FROM users WHERE email = ? AND id != ? AND deleted_at IS NULL",
+            email,
+            current.id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if email_exists.is_some() {
+            return Err(AppError::Conflict("auth.email_taken"));
+        }
+    }
+
+    // email baru harus diverifikasi ulang dan semua sesi lama dicabut
+    if email_changed {
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET name = ?, email = ?, locale = ?, email_verified_at = NULL, tokens_revoked_before = NOW()
+            WHERE id = ?
+            "#,
+            name,
+            email,
+            locale,
+            current.id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::from(e).with_conflict_message("auth.email_taken"))?;
+
+        sqlx::query!(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE user_id = ? AND revoked_at IS NULL
+            "#,
+            current.id
+        )
+        .execute(&mut *tx)
+        .await?;
+    } else {
+        sqlx::query!(
+            "UPDATE users SET name = ?, locale = ? WHERE id = ?",
+            name,
+            locale,
+            current.id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: USER_UPDATED,
+        target_id: current.id,
+        changes: diff(
+            &json!({ "name": current.name, "email": current.email, "locale": current.locale }),
+            &json!({ "name": name, "email": email, "locale": locale }),
+        ),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    let token = if email_changed {
+        // link yang dikirim ke email lama tidak boleh memverifikasi email baru
+        revoke_verification_tokens(&mut *tx, current.id).await?;
+        Some(issue_verification_token(&mut *tx, current.id).await?)
+    } else {
+        None
+    };
+
+    tx.commit().await?;
+
+    if email_changed {
+        revocations.forget_user(current.id);
+    }
+
+    if let Some(token) = token {
+        // kirim link verifikasi ke email baru
+        send_in_background(mailer, verification_mail(&config, &name, &email, &token));
+    }
+
+    let me = load_me(&db, claims.sub, &permissions).await?;
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("me.updated"), json!(me))),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/me/password",
+    tag = "me",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Password berhasil diganti"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn change_password(
+    Extension(db): Extension<MySqlPool>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(revocations): Extension<Arc<RevocationCache>>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<ChangePasswordRequest>,
+) -> ApiResult {
+    // get password saat ini
+    let user = sqlx::query!(
+        "SELECT id, password FROM users WHERE id = ? AND deleted_at IS NULL",
+        claims.sub
+    )
+    .fetch_optional(&db)
+    .await?
+    .ok_or_else(user_not_found)?;
+
+    // Verifikasi password saat ini Dengan Bcrypt
+    if !verify_password(&payload.current_password, &user.password)? {
+        return Err(AppError::invalid_field(
+            "current_password",
+            &t("password.current_incorrect"),
+        ));
+    }
+
+    // Hash Password Dengan Bcrypt
+    let password = hash_password(&payload.password, config.auth.bcrypt_cost)?;
+
+    // ganti password dan cabut semua sesi (termasuk sesi ini) dalam satu transaksi
+    let mut tx = db.begin().await?;
+
+    sqlx::query!(
+        "UPDATE users SET password = ?, tokens_revoked_before = NOW() WHERE id = ?",
+        password,
+        user.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = NOW()
+        WHERE user_id = ? AND revoked_at IS NULL
+        "#,
+        user.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: USER_PASSWORD_CHANGED,
+        target_id: user.id,
+        changes: json!({ "password_changed": true }),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    revocations.forget_user(user.id);
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("password.changed"), json!(null))),
+    ))
+}
@@ -0,0 +1,50 @@
+use axum::{
+    Extension,
+    http::{
+        HeaderMap,
+        header::{AUTHORIZATION, CONTENT_TYPE},
+    },
+    response::IntoResponse,
+};
+use metrics_exporter_prometheus::PrometheusHandle;
+use sqlx::MySqlPool;
+use std::sync::Arc;
+
+// import konfigurasi aplikasi
+use crate::config::app::AppConfig;
+
+// import util error & metrics
+use crate::utils::{app_error::AppError, metrics::record_pool_stats};
+
+// dibandingkan tanpa berhenti di byte pertama yang berbeda supaya token tidak bisa ditebak lewat timing
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// metrics format teks Prometheus (tanpa ApiResponse), hanya untuk scraper yang memegang metrics.token
+#[tracing::instrument(skip_all)]
+pub async fn metrics(
+    Extension(handle): Extension<PrometheusHandle>,
+    Extension(db): Extension<MySqlPool>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let expected = config.metrics.token.as_deref().unwrap_or_default();
+
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .ok_or_else(|| AppError::Unauthorized("auth.token_missing"))?;
+
+    if expected.is_empty() || !constant_time_eq(token.as_bytes(), expected.as_bytes()) {
+        return Err(AppError::Unauthorized("auth.token_invalid"));
+    }
+
+    record_pool_stats(&db).await;
+
+    Ok((
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        handle.render(),
+    ))
+}
@@ -1,3 +1,15 @@
+pub mod audit_handler;
+pub mod email_verification_handler;
+pub mod health_handler;
+pub mod jwks_handler;
 pub mod register_handler;
 pub mod login_handler;
+pub mod logout_handler;
+pub mod me_handler;
+pub mod metrics_handler;
+pub mod openapi_handler;
+pub mod password_handler;
+pub mod role_handler;
+pub mod token_handler;
+pub mod two_factor_handler;
 pub mod user_handler;
\ No newline at end of file
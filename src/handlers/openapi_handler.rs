@@ -0,0 +1,11 @@
+use axum::{Extension, http::header::CONTENT_TYPE, response::IntoResponse};
+use std::sync::Arc;
+
+// dokumen OpenAPI yang sudah dirender saat server start (tanpa ApiResponse)
+#[tracing::instrument(skip_all)]
+pub async fn openapi(Extension(spec): Extension<Arc<String>>) -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, "application/json")],
+        spec.as_str().to_owned(),
+    )
+}
@@ -0,0 +1,197 @@
+use axum::{Extension, Json, http::StatusCode};
+use chrono::Utc;
+use serde_json::json;
+use sqlx::MySqlPool;
+use std::sync::Arc;
+
+// import konfigurasi aplikasi
+use crate::config::app::AppConfig;
+
+// import pengirim dan template email
+use crate::mail::{
+    mailer::{MailSender, send_in_background},
+    templates::password_reset_mail,
+};
+
+// import schema request lupa & reset password
+use crate::schemas::password_schema::{ForgotPasswordRequest, ResetPasswordRequest};
+
+// import penerjemah pesan API
+use crate::i18n::messages::t;
+
+// import util error, audit, token reset password, revocation dan response API
+use crate::utils::{
+    app_error::{ApiResult, AppError},
+    audit::{AuditEntry, USER_PASSWORD_RESET, record as record_audit},
+    client_info::ClientInfo,
+    password::hash_password,
+    password_reset::{PASSWORD_RESET_PURPOSE, issue_password_reset_token},
+    response::ApiResponse,
+    revocation::RevocationCache,
+    signed_token,
+    validated_json::ValidatedJson,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/password/forgot",
+    tag = "auth",
+    request_body = ForgotPasswordRequest,
+    responses(
+        (status = 200, description = "Link reset password dikirim bila email terdaftar"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn forgot(
+    Extension(db): Extension<MySqlPool>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(mailer): Extension<Arc<dyn MailSender>>,
+    ValidatedJson(payload): ValidatedJson<ForgotPasswordRequest>,
+) -> ApiResult {
+    let user = sqlx::query!(
+        "SELECT id, name, email FROM users WHERE email = ? AND deleted_at IS NULL",
+        payload.email
+    )
+    .fetch_optional(&db)
+    .await?;
+
+    if let Some(user) = user {
+        let mut tx = db.begin().await?;
+
+        // hanya link reset terakhir yang berlaku
+        sqlx::query!(
+            "DELETE FROM password_reset_tokens WHERE user_id = ? AND used_at IS NULL",
+            user.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let token = issue_password_reset_token(&mut *tx, user.id).await?;
+
+        tx.commit().await?;
+
+        // email dikirim di background supaya waktu response tidak membocorkan email terdaftar
+        send_in_background(
+            mailer,
+            password_reset_mail(&config, &user.name, &user.email, &token),
+        );
+    }
+
+    // response sama untuk email terdaftar maupun tidak, supaya email tidak bisa ditebak
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("password.reset_link_sent"),
+            json!(null),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/password/reset",
+    tag = "auth",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password berhasil direset"),
+        (status = 400, description = "Request tidak valid"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn reset(
+    Extension(db): Extension<MySqlPool>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(revocations): Extension<Arc<RevocationCache>>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<ResetPasswordRequest>,
+) -> ApiResult {
+    let invalid = || AppError::BadRequest("password.reset_token_invalid");
+
+    // token dengan signature yang salah langsung ditolak tanpa query ke database
+    let token_hash =
+        signed_token::verify(PASSWORD_RESET_PURPOSE, &payload.token).ok_or_else(invalid)?;
+
+    // Hash Password Dengan Bcrypt
+    let password = hash_password(&payload.password, config.auth.bcrypt_cost)?;
+
+    // ganti password, pakai token dan cabut semua sesi dalam satu transaksi
+    let mut tx = db.begin().await?;
+
+    let stored = sqlx::query!(
+        r#"
+        SELECT id, user_id, expires_at, used_at
+        FROM password_reset_tokens
+        WHERE token_hash = ?
+        FOR UPDATE
+        "#,
+        token_hash
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .filter(|stored| stored.used_at.is_none() && stored.expires_at > Utc::now())
+    .ok_or_else(invalid)?;
+
+    let updated = sqlx::query!(
+        r#"
+        UPDATE users
+        SET password = ?, tokens_revoked_before = NOW()
+        WHERE id = ? AND deleted_at IS NULL
+        "#,
+        password,
+        stored.user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if updated.rows_affected() == 0 {
+        return Err(invalid());
+    }
+
+    // token ini dan token reset lain milik user tidak berlaku lagi
+    sqlx::query!(
+        "UPDATE password_reset_tokens SET used_at = NOW() WHERE user_id = ? AND used_at IS NULL",
+        stored.user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // cabut semua refresh token milik user
+    sqlx::query!(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = NOW()
+        WHERE user_id = ? AND revoked_at IS NULL
+        "#,
+        stored.user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let audit = AuditEntry {
+        actor_id: Some(stored.user_id),
+        action: USER_PASSWORD_RESET,
+        target_id: stored.user_id,
+        changes: json!({ "password_changed": true }),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    // access token yang masih tersimpan di cache dicek ulang ke database
+    revocations.forget_user(stored.user_id);
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("password.reset_success"),
+            json!(null),
+        )),
+    ))
+}
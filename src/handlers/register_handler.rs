@@ -4,10 +4,17 @@ use axum::{
     http::StatusCode,
 };
 use sqlx::MySqlPool;
-use bcrypt::hash;
-use validator::Validate;
-use std::collections::HashMap;
-use serde_json::{json, Value};
+use serde_json::json;
+use std::sync::Arc;
+
+// import konfigurasi aplikasi
+use crate::config::app::AppConfig;
+
+// import pengirim dan template email
+use crate::mail::{
+    mailer::{MailSender, send_in_background},
+    templates::verification_mail,
+};
 
 // import schema request dan response register
 use crate::schemas::register_schema::{
@@ -15,52 +22,46 @@ use crate::schemas::register_schema::{
     RegisterResponse,
 };
 
-// import util response API
-use crate::utils::response::ApiResponse;
+// import penerjemah pesan API
+use crate::i18n::messages::t;
+
+// import util error, audit, permission, verifikasi email dan response API
+use crate::utils::{
+    app_error::{ApiResult, AppError},
+    audit::{AuditEntry, USER_REGISTERED, diff, record as record_audit},
+    client_info::ClientInfo,
+    email_verification::issue_verification_token,
+    password::hash_password,
+    permission::assign_default_role,
+    response::ApiResponse,
+    validated_json::ValidatedJson,
+};
 
+#[utoipa::path(
+    post,
+    path = "/api/register",
+    tag = "auth",
+    request_body = RegisterRequest,
+    responses(
+        (status = 201, description = "Register berhasil", body = ApiResponse<RegisterResponse>),
+        (status = 409, description = "Email sudah terdaftar"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    )
+)]
+#[tracing::instrument(skip_all)]
 pub async fn register(
     Extension(db): Extension<MySqlPool>,
-    Json(payload): Json<RegisterRequest>,
-) -> (StatusCode, Json<ApiResponse<Value>>) {
-
-    // Validasi Request
-    if let Err(errors) = payload.validate() {
-        let mut field_errors: HashMap<String, Vec<String>> = HashMap::new();
-
-        // kumpulkan semua error dari validasi
-        for (field, errors) in errors.field_errors() {
-            let messages = errors
-                .iter()
-                .filter_map(|e| e.message.as_ref())
-                .map(|m| m.to_string())
-                .collect::<Vec<String>>();
-
-            field_errors.insert(field.to_string(), messages);
-        }
-
-        return (
-            // kirim response 422 Unprocessable Entity
-            StatusCode::UNPROCESSABLE_ENTITY,
-            Json(ApiResponse {
-                status: false,
-                message: "Validasi Gagal".to_string(),
-                data: Some(json!(field_errors)),
-            }),
-        );
-    }
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(mailer): Extension<Arc<dyn MailSender>>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<RegisterRequest>,
+) -> ApiResult {
 
     // Hash Password Dengan Bcrypt
-    let password = match hash(payload.password, 10) {
-        Ok(hashed) => hashed,
-        Err(_) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(
-                    "Gagal mengenkripsi password",
-                )),
-            );
-        }
-    };
+    let password = hash_password(&payload.password, config.auth.bcrypt_cost)?;
+
+    let mut tx = db.begin().await?;
 
     // Insert Data User ke Database
     let result = sqlx::query!(
@@ -69,74 +70,64 @@ pub async fn register(
         payload.email,
         password
     )
-    .execute(&db)
-    .await;
-
-    match result {
-        Ok(result) => {
-
-            // get id user yang baru saja dibuat
-            let user_id = result.last_insert_id() as i64;
-
-            // Ambil data user berdasarkan ID
-            let user = sqlx::query!(
-                r#"
-                SELECT id, name, email, created_at, updated_at
-                FROM users
-                WHERE id = ?
-                "#,
-                user_id
-            )
-            .fetch_one(&db)
-            .await;
-
-            match user {
-                Ok(user) => {
-                    let response = RegisterResponse {
-                        id: user.id,
-                        name: user.name,
-                        email: user.email,
-                        created_at: user.created_at,
-                        updated_at: user.updated_at,
-                    };
-
-                    (
-                        // kirim response 201 Created
-                        StatusCode::CREATED,
-                        Json(ApiResponse::success(
-                            "Register Berhasil!",
-                            json!(response),
-                        )),
-                    )
-                }
-                Err(_) => (
-
-                    // kirim response 500 Internal Server Error
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error(
-                        "Gagal mengambil data user",
-                    )),
-                ),
-            }
-        }
-        Err(e) => {
-            if e.to_string().contains("Duplicate entry") {
-                (
-                    // kirim response 409 Conflict
-                    StatusCode::CONFLICT,
-                    Json(ApiResponse::error(
-                        "Email sudah terdaftar",
-                    )),
-                )
-            } else {
-                (
-                    // kirim response 500 Internal Server Error
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error(
-                        "Register Gagal!",
-                    )),
-                )
-            }
-        }
-    }
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::from(e).with_conflict_message("auth.email_taken"))?;
+
+    // get id user yang baru saja dibuat
+    let user_id = result.last_insert_id() as i64;
+
+    // berikan role default, catat audit log dan buat token verifikasi email dalam transaksi yang sama
+    assign_default_role(&mut *tx, user_id).await?;
+
+    let audit = AuditEntry {
+        actor_id: Some(user_id),
+        action: USER_REGISTERED,
+        target_id: user_id,
+        changes: diff(
+            &json!({}),
+            &json!({ "name": payload.name, "email": payload.email }),
+        ),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    let verification_token = issue_verification_token(&mut *tx, user_id).await?;
+
+    tx.commit().await?;
+
+    // Ambil data user berdasarkan ID
+    let user = sqlx::query!(
+        r#"
+        SELECT id, name, email, created_at, updated_at
+        FROM users
+        WHERE id = ?
+        "#,
+        user_id
+    )
+    .fetch_one(&db)
+    .await?;
+
+    // kirim link verifikasi ke email user
+    send_in_background(
+        mailer,
+        verification_mail(&config, &user.name, &user.email, &verification_token),
+    );
+
+    let response = RegisterResponse {
+        id: user.id,
+        name: user.name,
+        email: user.email,
+        created_at: user.created_at,
+        updated_at: user.updated_at,
+    };
+
+    Ok((
+        // kirim response 201 Created
+        StatusCode::CREATED,
+        Json(ApiResponse::success(
+            &t("auth.register_success"),
+            json!(response),
+        )),
+    ))
 }
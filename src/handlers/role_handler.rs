@@ -0,0 +1,618 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use serde_json::json;
+use sqlx::{Executor, MySql, MySqlPool, Transaction};
+use std::collections::HashMap;
+
+// import model role dan permission
+use crate::models::{permission::Permission, role::Role};
+
+// import penerjemah pesan API
+use crate::i18n::messages::{t, t_with};
+
+// import util error, audit, permission dan response API
+use crate::utils::{
+    app_error::{ApiResult, AppError},
+    audit::{
+        AuditEntry, ROLE_CREATED, ROLE_DELETED, ROLE_UPDATED, USER_ROLES_UPDATED, diff,
+        record as record_audit,
+    },
+    client_info::ClientInfo,
+    jwt::Claims,
+    permission::{ADMIN_ROLE, is_built_in_role, load_role_names},
+    response::ApiResponse,
+    validated_json::ValidatedJson,
+};
+
+// import schema request dan response role
+use crate::schemas::role_schema::{
+    RoleResponse, RoleStoreRequest, RoleUpdateRequest, UserRolesRequest, UserRolesResponse,
+};
+
+fn role_not_found() -> AppError {
+    AppError::NotFound("role.not_found")
+}
+
+fn user_not_found() -> AppError {
+    AppError::NotFound("user.not_found")
+}
+
+fn built_in_role() -> AppError {
+    AppError::Conflict("role.built_in")
+}
+
+// nama permission milik role, terurut
+async fn load_permission_names<'e, E>(executor: E, role_id: i64) -> Result<Vec<String>, sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    let rows = sqlx::query!(
+        r#"
+        SELECT p.name
+        FROM role_permissions rp
+        JOIN permissions p ON p.id = rp.permission_id
+        WHERE rp.role_id = ?
+        ORDER BY p.name
+        "#,
+        role_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.name).collect())
+}
+
+// Ambil role beserta nama permission-nya
+async fn find_role(db: &MySqlPool, id: i64) -> Result<RoleResponse, AppError> {
+    let role = sqlx::query_as!(
+        Role,
+        r#"
+        SELECT id, name, description, created_at, updated_at
+        FROM roles
+        WHERE id = ?
+        "#,
+        id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(role_not_found)?;
+
+    let permissions = load_permission_names(db, id).await?;
+
+    Ok(RoleResponse {
+        id: role.id,
+        name: role.name,
+        description: role.description,
+        permissions,
+        created_at: role.created_at,
+        updated_at: role.updated_at,
+    })
+}
+
+// Ganti seluruh permission role, kembalikan nama permission yang tidak dikenal
+async fn sync_permissions(
+    tx: &mut Transaction<'_, MySql>,
+    role_id: i64,
+    permissions: &[String],
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query!("DELETE FROM role_permissions WHERE role_id = ?", role_id)
+        .execute(&mut **tx)
+        .await?;
+
+    for permission in permissions {
+        let result = sqlx::query!(
+            r#"
+            INSERT IGNORE INTO role_permissions (role_id, permission_id)
+            SELECT ?, id FROM permissions WHERE name = ?
+            "#,
+            role_id,
+            permission
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        // tidak ada baris yang masuk → cek apakah permission memang ada
+        if result.rows_affected() == 0 {
+            let exists = sqlx::query!("SELECT id FROM permissions WHERE name = ?", permission)
+                .fetch_optional(&mut **tx)
+                .await?;
+
+            if exists.is_none() {
+                return Ok(Some(permission.clone()));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn unknown_permission(permission: &str) -> AppError {
+    AppError::invalid_field(
+        "permissions",
+        &t_with("validation.permission_unknown", &[("name", permission)]),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/roles",
+    tag = "roles",
+    responses(
+        (status = 200, description = "List role", body = ApiResponse<Vec<RoleResponse>>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn index(Extension(db): Extension<MySqlPool>) -> ApiResult {
+    // Ambil seluruh data role
+    let roles = sqlx::query_as!(
+        Role,
+        r#"
+        SELECT id, name, description, created_at, updated_at
+        FROM roles
+        ORDER BY id ASC
+        "#
+    )
+    .fetch_all(&db)
+    .await?;
+
+    // Ambil seluruh pasangan role → permission sekaligus
+    let pairs = sqlx::query!(
+        r#"
+        SELECT rp.role_id, p.name
+        FROM role_permissions rp
+        JOIN permissions p ON p.id = rp.permission_id
+        ORDER BY p.name
+        "#
+    )
+    .fetch_all(&db)
+    .await?;
+
+    let mut permissions: HashMap<i64, Vec<String>> = HashMap::new();
+    for pair in pairs {
+        permissions.entry(pair.role_id).or_default().push(pair.name);
+    }
+
+    let response = roles
+        .into_iter()
+        .map(|role| RoleResponse {
+            permissions: permissions.remove(&role.id).unwrap_or_default(),
+            id: role.id,
+            name: role.name,
+            description: role.description,
+            created_at: role.created_at,
+            updated_at: role.updated_at,
+        })
+        .collect::<Vec<RoleResponse>>();
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("role.list"), json!(response))),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/roles",
+    tag = "roles",
+    request_body = RoleStoreRequest,
+    responses(
+        (status = 201, description = "Role berhasil ditambahkan", body = ApiResponse<RoleResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 409, description = "Nama role sudah terdaftar"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn store(
+    Extension(db): Extension<MySqlPool>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<RoleStoreRequest>,
+) -> ApiResult {
+    let mut tx = db.begin().await?;
+
+    // Insert Data Role ke Database
+    let role_id = sqlx::query!(
+        "INSERT INTO roles (name, description) VALUES (?, ?)",
+        payload.name,
+        payload.description
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::from(e).with_conflict_message("role.name_taken"))?
+    .last_insert_id() as i64;
+
+    // Simpan permission role
+    if let Some(permission) = sync_permissions(&mut tx, role_id, &payload.permissions).await? {
+        return Err(unknown_permission(&permission));
+    }
+
+    // catat audit log dalam transaksi yang sama
+    let permissions = load_permission_names(&mut *tx, role_id).await?;
+
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: ROLE_CREATED,
+        target_id: role_id,
+        changes: json!({
+            "name": payload.name,
+            "description": payload.description,
+            "permissions": permissions,
+        }),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    let role = find_role(&db, role_id).await?;
+
+    Ok((
+        // kirim response 201 Created
+        StatusCode::CREATED,
+        Json(ApiResponse::success(&t("role.created"), json!(role))),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/roles/{id}",
+    tag = "roles",
+    params(("id" = i64, Path, description = "ID role")),
+    responses(
+        (status = 200, description = "Detail role", body = ApiResponse<RoleResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 404, description = "Data tidak ditemukan"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn show(Path(id): Path<i64>, Extension(db): Extension<MySqlPool>) -> ApiResult {
+    let role = find_role(&db, id).await?;
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("role.detail"), json!(role))),
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/roles/{id}",
+    tag = "roles",
+    params(("id" = i64, Path, description = "ID role")),
+    request_body = RoleUpdateRequest,
+    responses(
+        (status = 200, description = "Role berhasil diperbarui", body = ApiResponse<RoleResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 404, description = "Data tidak ditemukan"),
+        (status = 409, description = "Nama role sudah terdaftar, atau role bawaan diganti nama / permission admin diubah"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn update(
+    Path(id): Path<i64>,
+    Extension(db): Extension<MySqlPool>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<RoleUpdateRequest>,
+) -> ApiResult {
+    let mut tx = db.begin().await?;
+
+    // data sebelum diubah, dikunci sampai transaksi selesai
+    let current = sqlx::query!(
+        "SELECT id, name, description FROM roles WHERE id = ? FOR UPDATE",
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(role_not_found)?;
+
+    // member dipakai assign_default_role, admin dipakai untuk mengelola role
+    if is_built_in_role(&current.name) && payload.name != current.name {
+        return Err(built_in_role());
+    }
+
+    let before_permissions = load_permission_names(&mut *tx, id).await?;
+
+    // Update role
+    sqlx::query!(
+        "UPDATE roles SET name = ?, description = ? WHERE id = ?",
+        payload.name,
+        payload.description,
+        id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::from(e).with_conflict_message("role.name_taken"))?;
+
+    // Ganti permission role
+    if let Some(permission) = sync_permissions(&mut tx, id, &payload.permissions).await? {
+        return Err(unknown_permission(&permission));
+    }
+
+    let after_permissions = load_permission_names(&mut *tx, id).await?;
+
+    // admin tanpa roles.manage berarti tidak ada lagi yang bisa mengelola role
+    if current.name == ADMIN_ROLE && after_permissions != before_permissions {
+        return Err(AppError::Conflict("role.admin_permissions_locked"));
+    }
+
+    // catat audit log dalam transaksi yang sama
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: ROLE_UPDATED,
+        target_id: id,
+        changes: diff(
+            &json!({
+                "name": current.name,
+                "description": current.description,
+                "permissions": before_permissions,
+            }),
+            &json!({
+                "name": payload.name,
+                "description": payload.description,
+                "permissions": after_permissions,
+            }),
+        ),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    let role = find_role(&db, id).await?;
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("role.updated"), json!(role))),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/roles/{id}",
+    tag = "roles",
+    params(("id" = i64, Path, description = "ID role")),
+    responses(
+        (status = 200, description = "Role berhasil dihapus"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 404, description = "Data tidak ditemukan"),
+        (status = 409, description = "Role bawaan tidak boleh dihapus"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn destroy(
+    Path(id): Path<i64>,
+    Extension(db): Extension<MySqlPool>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+) -> ApiResult {
+    let mut tx = db.begin().await?;
+
+    // data sebelum dihapus, untuk audit log
+    let current = sqlx::query!(
+        "SELECT id, name, description FROM roles WHERE id = ? FOR UPDATE",
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(role_not_found)?;
+
+    if is_built_in_role(&current.name) {
+        return Err(built_in_role());
+    }
+
+    let permissions = load_permission_names(&mut *tx, id).await?;
+
+    // Hapus role, relasi ke user & permission ikut terhapus (ON DELETE CASCADE)
+    sqlx::query!("DELETE FROM roles WHERE id = ?", id)
+        .execute(&mut *tx)
+        .await?;
+
+    // catat audit log dalam transaksi yang sama
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: ROLE_DELETED,
+        target_id: id,
+        changes: json!({
+            "name": current.name,
+            "description": current.description,
+            "permissions": permissions,
+        }),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("role.deleted"), json!(null))),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/permissions",
+    tag = "roles",
+    responses(
+        (status = 200, description = "List permission", body = ApiResponse<Vec<Permission>>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn permissions(Extension(db): Extension<MySqlPool>) -> ApiResult {
+    // Ambil seluruh permission yang dikenal aplikasi
+    let permissions = sqlx::query_as!(
+        Permission,
+        "SELECT id, name, description FROM permissions ORDER BY name"
+    )
+    .fetch_all(&db)
+    .await?;
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("permission.list"),
+            json!(permissions),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/roles",
+    tag = "roles",
+    params(("id" = i64, Path, description = "ID user")),
+    responses(
+        (status = 200, description = "Role milik user", body = ApiResponse<UserRolesResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 404, description = "Data tidak ditemukan"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn user_roles(Path(id): Path<i64>, Extension(db): Extension<MySqlPool>) -> ApiResult {
+    // Cek user exist
+    sqlx::query!(
+        "SELECT id FROM users WHERE id = ? AND deleted_at IS NULL",
+        id
+    )
+    .fetch_optional(&db)
+    .await?
+    .ok_or_else(user_not_found)?;
+
+    let roles = load_role_names(&db, id).await?;
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("role.user_roles"),
+            json!(UserRolesResponse { user_id: id, roles }),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}/roles",
+    tag = "roles",
+    params(("id" = i64, Path, description = "ID user")),
+    request_body = UserRolesRequest,
+    responses(
+        (status = 200, description = "Role user berhasil diperbarui", body = ApiResponse<UserRolesResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 404, description = "Data tidak ditemukan"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn assign_user_roles(
+    Path(id): Path<i64>,
+    Extension(db): Extension<MySqlPool>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<UserRolesRequest>,
+) -> ApiResult {
+    let mut tx = db.begin().await?;
+
+    // Cek user exist
+    sqlx::query!(
+        "SELECT id FROM users WHERE id = ? AND deleted_at IS NULL FOR UPDATE",
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(user_not_found)?;
+
+    // role sebelum diubah, untuk audit log
+    let before = load_role_names(&mut *tx, id).await?;
+
+    // Ganti seluruh role user
+    sqlx::query!("DELETE FROM user_roles WHERE user_id = ?", id)
+        .execute(&mut *tx)
+        .await?;
+
+    for role in &payload.roles {
+        let result = sqlx::query!(
+            r#"
+            INSERT IGNORE INTO user_roles (user_id, role_id)
+            SELECT ?, id FROM roles WHERE name = ?
+            "#,
+            id,
+            role
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // tidak ada baris yang masuk → cek apakah role memang ada
+        if result.rows_affected() == 0 {
+            let exists = sqlx::query!("SELECT id FROM roles WHERE name = ?", role)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+            if exists.is_none() {
+                return Err(AppError::invalid_field(
+                    "roles",
+                    &t_with("validation.role_unknown", &[("name", role)]),
+                ));
+            }
+        }
+    }
+
+    // catat audit log dalam transaksi yang sama
+    let after = load_role_names(&mut *tx, id).await?;
+
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: USER_ROLES_UPDATED,
+        target_id: id,
+        changes: diff(&json!({ "roles": before }), &json!({ "roles": after })),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("role.user_roles_updated"),
+            json!(UserRolesResponse {
+                user_id: id,
+                roles: after,
+            }),
+        )),
+    ))
+}
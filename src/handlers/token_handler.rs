@@ -0,0 +1,150 @@
+use axum::{
+    Extension,
+    Json,
+    http::StatusCode,
+};
+use sqlx::MySqlPool;
+use chrono::Utc;
+use serde_json::json;
+use std::sync::Arc;
+
+// import konfigurasi aplikasi
+use crate::config::app::AppConfig;
+
+// import schema request dan response token
+use crate::schemas::token_schema::{
+    RefreshTokenRequest,
+    TokenResponse,
+};
+
+// import penerjemah pesan API
+use crate::i18n::messages::t;
+
+// import util error, jwt, refresh token dan response API
+use crate::utils::{
+    app_error::{ApiResult, AppError},
+    jwt::{UserClaims, generate_token},
+    permission::load_role_names,
+    refresh_token::{hash_token, issue_refresh_token},
+    response::ApiResponse,
+    validated_json::ValidatedJson,
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/token/refresh",
+    tag = "auth",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "Token berhasil diperbarui", body = ApiResponse<TokenResponse>),
+        (status = 401, description = "Refresh token tidak valid, kedaluwarsa atau sudah dipakai"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn refresh(
+    Extension(db): Extension<MySqlPool>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    ValidatedJson(payload): ValidatedJson<RefreshTokenRequest>,
+) -> ApiResult {
+    let invalid = || AppError::Unauthorized("token.refresh_invalid");
+
+    let mut tx = db.begin().await?;
+
+    // get refresh token berdasarkan hash, dikunci selama proses rotasi
+    let stored = sqlx::query!(
+        r#"
+        SELECT id, user_id, family_id, mfa_verified AS `mfa_verified: bool`, expires_at, revoked_at
+        FROM refresh_tokens
+        WHERE token_hash = ?
+        FOR UPDATE
+        "#,
+        hash_token(&payload.refresh_token)
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(invalid)?;
+
+    // token yang sudah dirotasi dipakai lagi, cabut seluruh family
+    if stored.revoked_at.is_some() {
+        sqlx::query!(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE family_id = ? AND revoked_at IS NULL
+            "#,
+            stored.family_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        return Err(AppError::Unauthorized("token.refresh_reused"));
+    }
+
+    if stored.expires_at <= Utc::now() {
+        return Err(AppError::Unauthorized("token.refresh_expired"));
+    }
+
+    // data user terbaru untuk claim access token, user yang sudah dihapus tidak bisa refresh
+    let user = sqlx::query!(
+        "SELECT name, email FROM users WHERE id = ? AND deleted_at IS NULL",
+        stored.user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(invalid)?;
+
+    let roles = load_role_names(&mut *tx, stored.user_id).await?;
+
+    // terbitkan refresh token baru dalam family yang sama
+    let (new_id, refresh_token) = issue_refresh_token(
+        &mut *tx,
+        stored.user_id,
+        &stored.family_id,
+        stored.mfa_verified,
+        config.auth.refresh_token_ttl_days,
+    )
+    .await?;
+
+    // tandai token lama sudah dirotasi
+    sqlx::query!(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = NOW(), replaced_by = ?
+        WHERE id = ?
+        "#,
+        new_id,
+        stored.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let user_claims = UserClaims {
+        name: user.name,
+        email: user.email,
+        roles,
+        mfa: stored.mfa_verified,
+    };
+
+    // generate access token JWT baru
+    let token = generate_token(stored.user_id, user_claims)?;
+
+    let response = TokenResponse {
+        token,
+        refresh_token,
+    };
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("token.refreshed"),
+            json!(response),
+        )),
+    ))
+}
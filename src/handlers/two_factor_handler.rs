@@ -0,0 +1,450 @@
+use axum::{Extension, Json, http::StatusCode};
+use serde_json::json;
+use sqlx::MySqlPool;
+use std::sync::Arc;
+
+// import konfigurasi aplikasi
+use crate::config::app::AppConfig;
+
+// import helper login untuk menerbitkan token setelah 2FA lolos
+use crate::handlers::login_handler::{complete_login, too_many_attempts};
+
+// import schema request dan response 2FA
+use crate::schemas::{
+    login_schema::{LoginResponse, TwoFactorLoginRequest},
+    two_factor_schema::{
+        RecoveryCodesResponse, TwoFactorConfirmRequest, TwoFactorDisableRequest,
+        TwoFactorSetupResponse,
+    },
+};
+
+// import penerjemah pesan API
+use crate::i18n::messages::t;
+
+// import util audit, error, jwt, pembatas login, totp dan response API
+use crate::utils::{
+    app_error::{ApiResult, AppError},
+    audit::{
+        AuditEntry, USER_TWO_FACTOR_DISABLED, USER_TWO_FACTOR_ENABLED, record as record_audit,
+    },
+    client_info::ClientInfo,
+    jwt::{Claims, verify_mfa_token},
+    login_throttle::LoginThrottle,
+    metrics::{LOGIN_FAILURE, LOGIN_SUCCESS, LOGIN_THROTTLED, LOGIN_TWO_FACTOR, record_login},
+    password::verify_password,
+    refresh_token::hash_token,
+    response::ApiResponse,
+    revocation::{consume_mfa_token, is_mfa_token_used},
+    totp,
+    validated_json::ValidatedJson,
+};
+
+fn user_not_found() -> AppError {
+    AppError::NotFound("user.not_found")
+}
+
+fn already_enabled() -> AppError {
+    AppError::Conflict("two_factor.already_enabled")
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/me/2fa/setup",
+    tag = "me",
+    responses(
+        (status = 200, description = "Secret & otpauth URI untuk aplikasi authenticator", body = ApiResponse<TwoFactorSetupResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 409, description = "2FA sudah aktif"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn setup(
+    Extension(db): Extension<MySqlPool>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult {
+    let user = sqlx::query!(
+        r#"
+        SELECT email, two_factor_confirmed_at IS NOT NULL AS `two_factor_enabled!: bool`
+        FROM users
+        WHERE id = ? AND deleted_at IS NULL
+        "#,
+        claims.sub
+    )
+    .fetch_optional(&db)
+    .await?
+    .ok_or_else(user_not_found)?;
+
+    if user.two_factor_enabled {
+        return Err(already_enabled());
+    }
+
+    // secret baru belum berlaku sampai dikonfirmasi dengan kode yang benar
+    let secret = totp::generate_secret();
+
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET two_factor_secret = ?, two_factor_last_used_step = NULL
+        WHERE id = ?
+        "#,
+        secret,
+        claims.sub
+    )
+    .execute(&db)
+    .await?;
+
+    let response = TwoFactorSetupResponse {
+        otpauth_uri: totp::otpauth_uri(&config.app.name, &user.email, &secret),
+        secret,
+    };
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("two_factor.setup"),
+            json!(response),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/me/2fa/confirm",
+    tag = "me",
+    request_body = TwoFactorConfirmRequest,
+    responses(
+        (status = 200, description = "2FA aktif, recovery code hanya ditampilkan sekali", body = ApiResponse<RecoveryCodesResponse>),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 409, description = "2FA sudah aktif"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn confirm(
+    Extension(db): Extension<MySqlPool>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<TwoFactorConfirmRequest>,
+) -> ApiResult {
+    let user = sqlx::query!(
+        r#"
+        SELECT two_factor_secret, two_factor_confirmed_at IS NOT NULL AS `two_factor_enabled!: bool`
+        FROM users
+        WHERE id = ? AND deleted_at IS NULL
+        "#,
+        claims.sub
+    )
+    .fetch_optional(&db)
+    .await?
+    .ok_or_else(user_not_found)?;
+
+    if user.two_factor_enabled {
+        return Err(already_enabled());
+    }
+
+    let Some(secret) = user.two_factor_secret else {
+        return Err(AppError::BadRequest("two_factor.setup_required"));
+    };
+
+    let Some(step) = totp::verify(&secret, &payload.code, None) else {
+        return Err(AppError::invalid_field(
+            "code",
+            &t("two_factor.code_invalid"),
+        ));
+    };
+
+    let recovery_codes = totp::generate_recovery_codes();
+
+    // aktifkan 2FA dan simpan hash recovery code dalam satu transaksi
+    let mut tx = db.begin().await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET two_factor_confirmed_at = NOW(), two_factor_last_used_step = ?
+        WHERE id = ?
+        "#,
+        step,
+        claims.sub
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM two_factor_recovery_codes WHERE user_id = ?",
+        claims.sub
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    for code in &recovery_codes {
+        sqlx::query!(
+            "INSERT INTO two_factor_recovery_codes (user_id, code_hash) VALUES (?, ?)",
+            claims.sub,
+            hash_token(code)
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: USER_TWO_FACTOR_ENABLED,
+        target_id: claims.sub,
+        changes: json!({}),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    let response = RecoveryCodesResponse { recovery_codes };
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("two_factor.enabled"),
+            json!(response),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/me/2fa",
+    tag = "me",
+    request_body = TwoFactorDisableRequest,
+    responses(
+        (status = 200, description = "2FA berhasil dinonaktifkan"),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn disable(
+    Extension(db): Extension<MySqlPool>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<TwoFactorDisableRequest>,
+) -> ApiResult {
+    let user = sqlx::query!(
+        r#"
+        SELECT password, two_factor_confirmed_at IS NOT NULL AS `two_factor_enabled!: bool`
+        FROM users
+        WHERE id = ? AND deleted_at IS NULL
+        "#,
+        claims.sub
+    )
+    .fetch_optional(&db)
+    .await?
+    .ok_or_else(user_not_found)?;
+
+    if !user.two_factor_enabled {
+        return Err(AppError::BadRequest("two_factor.not_enabled"));
+    }
+
+    // Verifikasi password Dengan Bcrypt
+    if !verify_password(&payload.password, &user.password)? {
+        return Err(AppError::invalid_field(
+            "password",
+            &t("password.incorrect"),
+        ));
+    }
+
+    let mut tx = db.begin().await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET two_factor_secret = NULL,
+            two_factor_confirmed_at = NULL,
+            two_factor_last_used_step = NULL
+        WHERE id = ?
+        "#,
+        claims.sub
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM two_factor_recovery_codes WHERE user_id = ?",
+        claims.sub
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: USER_TWO_FACTOR_DISABLED,
+        target_id: claims.sub,
+        changes: json!({}),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("two_factor.disabled"), json!(null))),
+    ))
+}
+
+// langkah kedua login: tukar token "mfa pending" + kode 2FA dengan access token
+#[utoipa::path(
+    post,
+    path = "/api/login/2fa",
+    tag = "auth",
+    request_body = TwoFactorLoginRequest,
+    responses(
+        (status = 200, description = "Login berhasil", body = ApiResponse<LoginResponse>),
+        (status = 401, description = "Token 2FA atau kode salah"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn login(
+    Extension(db): Extension<MySqlPool>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(throttle): Extension<Arc<LoginThrottle>>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<TwoFactorLoginRequest>,
+) -> ApiResult {
+    if payload.code.is_none() && payload.recovery_code.is_none() {
+        return Err(AppError::invalid_field(
+            "code",
+            &t("validation.two_factor_code_required"),
+        ));
+    }
+
+    let invalid_token = || AppError::Unauthorized("two_factor.token_invalid");
+
+    let mfa = verify_mfa_token(&payload.mfa_token).map_err(|_| invalid_token())?;
+
+    // token "mfa pending" sekali pakai
+    if is_mfa_token_used(&db, &mfa).await? {
+        return Err(invalid_token());
+    }
+
+    let user = sqlx::query!(
+        r#"
+        SELECT id, name, email, two_factor_secret, two_factor_last_used_step
+        FROM users
+        WHERE id = ? AND deleted_at IS NULL AND two_factor_confirmed_at IS NOT NULL
+        "#,
+        mfa.sub
+    )
+    .fetch_optional(&db)
+    .await?
+    .ok_or_else(invalid_token)?;
+
+    // kode 2FA ikut dibatasi seperti password supaya tidak bisa ditebak
+    if let Some(retry_after) = throttle
+        .retry_after(&user.email, client.ip.as_deref())
+        .await?
+    {
+        record_login(LOGIN_TWO_FACTOR, LOGIN_THROTTLED);
+        return Err(too_many_attempts(retry_after));
+    }
+
+    let verified = if let Some(code) = &payload.code {
+        let secret = user.two_factor_secret.as_deref().unwrap_or_default();
+
+        match totp::verify(secret, code, user.two_factor_last_used_step) {
+            Some(step) => {
+                // step disimpan supaya kode yang sama tidak bisa dipakai dua kali
+                let updated = sqlx::query!(
+                    r#"
+                    UPDATE users
+                    SET two_factor_last_used_step = ?
+                    WHERE id = ?
+                    AND (two_factor_last_used_step IS NULL OR two_factor_last_used_step < ?)
+                    "#,
+                    step,
+                    user.id,
+                    step
+                )
+                .execute(&db)
+                .await?;
+
+                updated.rows_affected() > 0
+            }
+            None => false,
+        }
+    } else {
+        let recovery_code = payload.recovery_code.as_deref().unwrap_or_default();
+
+        // recovery code hanya bisa dipakai satu kali
+        let used = sqlx::query!(
+            r#"
+            UPDATE two_factor_recovery_codes
+            SET used_at = NOW()
+            WHERE user_id = ? AND code_hash = ? AND used_at IS NULL
+            LIMIT 1
+            "#,
+            user.id,
+            hash_token(&totp::normalize_recovery_code(recovery_code))
+        )
+        .execute(&db)
+        .await?;
+
+        used.rows_affected() > 0
+    };
+
+    if !verified {
+        record_login(LOGIN_TWO_FACTOR, LOGIN_FAILURE);
+
+        let locked = match throttle
+            .record_failure(&user.email, client.ip.as_deref())
+            .await
+        {
+            Ok(locked) => locked,
+            Err(e) => {
+                tracing::error!(error = %e, "Database error");
+                None
+            }
+        };
+
+        // token yang terlalu sering salah dibakar, user harus login ulang dengan password
+        match throttle.record_mfa_failure(&mfa.jti).await {
+            Ok(true) => {
+                consume_mfa_token(&db, &mfa).await?;
+            }
+            Ok(false) => {}
+            Err(e) => tracing::error!(error = %e, "Database error"),
+        }
+
+        // kegagalan yang membuat akun/IP terkunci langsung dijawab 429 dengan Retry-After
+        if let Some(retry_after) = locked {
+            return Err(too_many_attempts(retry_after));
+        }
+
+        return Err(AppError::Unauthorized("two_factor.code_invalid"));
+    }
+
+    // request paralel dengan token yang sama hanya satu yang lolos
+    if !consume_mfa_token(&db, &mfa).await? {
+        return Err(invalid_token());
+    }
+
+    if let Err(e) = throttle.record_success(&user.email).await {
+        tracing::error!(error = %e, "Database error");
+    }
+
+    record_login(LOGIN_TWO_FACTOR, LOGIN_SUCCESS);
+
+    complete_login(&db, &config, user.id, user.name, user.email, true).await
+}
@@ -1,197 +1,507 @@
-use axum::{Extension, Json, extract::Path, http::StatusCode};
-use bcrypt::hash;
-use serde_json::{Value, json};
-use sqlx::MySqlPool;
-use std::collections::HashMap;
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, rejection::QueryRejection},
+    http::StatusCode,
+};
+use serde_json::json;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+use std::sync::Arc;
 use validator::Validate;
 
+// import konfigurasi aplikasi
+use crate::config::app::AppConfig;
+
+// import pengirim dan template email
+use crate::mail::{
+    mailer::{MailSender, send_in_background},
+    templates::verification_mail,
+};
+
 // import model user
 use crate::models::user::User;
 
-// import util response API
-use crate::utils::response::ApiResponse;
+// import penerjemah pesan API
+use crate::i18n::messages::t;
+
+// import util audit, error, pembatas login, pagination, permission dan response API
+use crate::utils::{
+    app_error::{ApiResult, AppError},
+    audit::{
+        AuditEntry, USER_CREATED, USER_DELETED, USER_PURGED, USER_RESTORED, USER_UNLOCKED,
+        USER_UPDATED, diff, record as record_audit,
+    },
+    client_info::ClientInfo,
+    email_verification::{issue_verification_token, revoke_verification_tokens},
+    jwt::Claims,
+    login_throttle::LoginThrottle,
+    pagination::{
+        DEFAULT_PER_PAGE, PaginationLinks, PaginationMeta, last_page, like_contains, parse_sort,
+    },
+    password::hash_password,
+    permission::assign_default_role,
+    response::ApiResponse,
+    revocation::RevocationCache,
+    search::{boolean_query, fulltext_terms, highlight, like_prefix, short_terms},
+    validated_json::ValidatedJson,
+};
 
 // import schema request dan response user
-use crate::schemas::user_schema::{UserResponse, UserStoreRequest, UserUpdateRequest};
+use crate::schemas::user_schema::{
+    TrashedUserResponse, UserIndexQuery, UserResponse, UserSearchQuery, UserSearchResponse,
+    UserStoreRequest, UserTrashQuery, UserUpdateRequest,
+};
 
-pub async fn index(Extension(db): Extension<MySqlPool>) -> (StatusCode, Json<ApiResponse<Value>>) {
-    // Ambil seluruh data user
-    let users = match sqlx::query_as!(
-        User,
-        r#"
-        SELECT id, name, email, created_at, updated_at
-        FROM users
-        ORDER BY id DESC
-        "#
+// kolom yang boleh dipakai untuk sort
+const SORT_FIELDS: &[&str] = &["id", "name", "email", "created_at"];
+
+// tambahkan filter name, email & rentang created_at ke query
+fn push_filters(builder: &mut QueryBuilder<'_, MySql>, query: &UserIndexQuery) {
+    if let Some(name) = query.name.as_deref().filter(|v| !v.is_empty()) {
+        builder
+            .push(" AND name LIKE ")
+            .push_bind(like_contains(name));
+    }
+
+    if let Some(email) = query.email.as_deref().filter(|v| !v.is_empty()) {
+        builder
+            .push(" AND email LIKE ")
+            .push_bind(like_contains(email));
+    }
+
+    if let Some(from) = query.created_from {
+        builder.push(" AND created_at >= ").push_bind(from);
+    }
+
+    if let Some(to) = query.created_to {
+        // sampai akhir hari created_to
+        builder
+            .push(" AND created_at < DATE_ADD(")
+            .push_bind(to)
+            .push(", INTERVAL 1 DAY)");
+    }
+}
+
+// link halaman lain dengan filter & sort yang sama
+fn page_link(query: &UserIndexQuery, page: Option<u32>, cursor: Option<i64>) -> String {
+    let query = UserIndexQuery {
+        page,
+        cursor,
+        ..query.clone()
+    };
+
+    format!(
+        "/api/users?{}",
+        serde_urlencoded::to_string(&query).unwrap_or_default()
     )
-    .fetch_all(&db)
-    .await
-    {
-        Ok(users) => users,
-        Err(e) => {
-            eprintln!("Database error: {}", e);
-            return (
-                // kirim response 500 Internal Server Error
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("Gagal mengambil data user")),
-            );
+}
+
+// link halaman trash lain dengan per_page yang sama
+fn trash_page_link(query: &UserTrashQuery, page: u32) -> String {
+    let query = UserTrashQuery {
+        page: Some(page),
+        ..query.clone()
+    };
+
+    format!(
+        "/api/users/trash?{}",
+        serde_urlencoded::to_string(&query).unwrap_or_default()
+    )
+}
+
+fn invalid_query(_: QueryRejection) -> AppError {
+    AppError::BadRequest("error.invalid_query")
+}
+
+fn user_not_found() -> AppError {
+    AppError::NotFound("user.not_found")
+}
+
+fn trashed_user_not_found() -> AppError {
+    AppError::NotFound("user.not_found_in_trash")
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users",
+    tag = "users",
+    params(UserIndexQuery),
+    responses(
+        (status = 200, description = "List user dengan pagination", body = ApiResponse<Vec<User>>),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn index(
+    Extension(db): Extension<MySqlPool>,
+    query: Result<Query<UserIndexQuery>, QueryRejection>,
+) -> ApiResult {
+    let Query(query) = query.map_err(invalid_query)?;
+
+    // Validasi Request
+    query.validate()?;
+
+    let Some(sort) = parse_sort(query.sort.as_deref().unwrap_or("-id"), SORT_FIELDS) else {
+        return Err(AppError::invalid_field(
+            "sort",
+            &t("validation.sort_invalid"),
+        ));
+    };
+
+    // keyset pagination hanya bisa mengikuti urutan id
+    if query.cursor.is_some() && sort.column != "id" {
+        return Err(AppError::invalid_field(
+            "cursor",
+            &t("validation.cursor_requires_id_sort"),
+        ));
+    }
+
+    if query.cursor.is_some() && query.page.is_some() {
+        return Err(AppError::invalid_field(
+            "cursor",
+            &t("validation.page_or_cursor"),
+        ));
+    }
+
+    let per_page = query.per_page.unwrap_or(DEFAULT_PER_PAGE);
+    let page = query.page.unwrap_or(1);
+
+    // Hitung total user sesuai filter
+    let mut count =
+        QueryBuilder::<MySql>::new("SELECT COUNT(*) FROM users WHERE deleted_at IS NULL");
+    push_filters(&mut count, &query);
+
+    let total: i64 = count.build_query_scalar().fetch_one(&db).await?;
+
+    // Ambil data user satu halaman (+1 untuk cek halaman berikutnya)
+    let mut select = QueryBuilder::<MySql>::new(
+        "SELECT id, name, email, created_at, updated_at FROM users WHERE deleted_at IS NULL",
+    );
+    push_filters(&mut select, &query);
+
+    if let Some(cursor) = query.cursor {
+        select
+            .push(if sort.descending {
+                " AND id < "
+            } else {
+                " AND id > "
+            })
+            .push_bind(cursor);
+    }
+
+    // kolom sort berasal dari whitelist, aman disisipkan langsung
+    select.push(format!(" ORDER BY {} {}", sort.column, sort.direction()));
+    if sort.column != "id" {
+        select.push(format!(", id {}", sort.direction()));
+    }
+
+    select.push(" LIMIT ").push_bind(per_page as i64 + 1);
+    if query.cursor.is_none() {
+        select
+            .push(" OFFSET ")
+            .push_bind((page as i64 - 1) * per_page as i64);
+    }
+
+    let mut users: Vec<User> = select.build_query_as().fetch_all(&db).await?;
+
+    let has_more = users.len() > per_page as usize;
+    users.truncate(per_page as usize);
+
+    let next_cursor = match (has_more, sort.column) {
+        (true, "id") => users.last().map(|user| user.id),
+        _ => None,
+    };
+
+    let meta = if query.cursor.is_some() {
+        PaginationMeta {
+            total,
+            per_page,
+            current_page: None,
+            last_page: None,
+            next_cursor,
+            links: PaginationLinks {
+                first: page_link(&query, Some(1), None),
+                prev: None,
+                next: next_cursor.map(|cursor| page_link(&query, None, Some(cursor))),
+                last: None,
+            },
+        }
+    } else {
+        let last = last_page(total, per_page);
+
+        PaginationMeta {
+            total,
+            per_page,
+            current_page: Some(page),
+            last_page: Some(last),
+            next_cursor,
+            links: PaginationLinks {
+                first: page_link(&query, Some(1), None),
+                prev: (page > 1).then(|| page_link(&query, Some(page - 1), None)),
+                next: has_more.then(|| page_link(&query, Some(page + 1), None)),
+                last: Some(page_link(&query, Some(last), None)),
+            },
         }
     };
 
-    (
+    Ok((
         // kirim response 200 OK
         StatusCode::OK,
-        Json(ApiResponse::success("List user", json!(users))),
-    )
+        Json(ApiResponse::paginated(&t("user.list"), json!(users), meta)),
+    ))
 }
 
-pub async fn store(
+// jumlah hasil pencarian bila limit tidak dikirim
+const SEARCH_LIMIT: u32 = 20;
+
+// hasil pencarian FULLTEXT beserta skor relevansi
+#[derive(sqlx::FromRow)]
+struct ScoredUser {
+    #[sqlx(flatten)]
+    user: User,
+    score: f64,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/search",
+    tag = "users",
+    params(UserSearchQuery),
+    responses(
+        (status = 200, description = "Hasil pencarian user", body = ApiResponse<Vec<UserSearchResponse>>),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn search(
     Extension(db): Extension<MySqlPool>,
-    Json(payload): Json<UserStoreRequest>,
-) -> (StatusCode, Json<ApiResponse<Value>>) {
+    query: Result<Query<UserSearchQuery>, QueryRejection>,
+) -> ApiResult {
+    let Query(query) = query.map_err(invalid_query)?;
+
     // Validasi Request
-    if let Err(errors) = payload.validate() {
-        let mut field_errors: HashMap<String, Vec<String>> = HashMap::new();
-
-        // kumpulkan semua error dari validasi
-        for (field, errors) in errors.field_errors() {
-            let messages = errors
-                .iter()
-                .filter_map(|e| e.message.as_ref())
-                .map(|m| m.to_string())
-                .collect::<Vec<String>>();
-
-            field_errors.insert(field.to_string(), messages);
-        }
+    query.validate()?;
 
-        return (
-            // kirim response 422 Unprocessable Entity
-            StatusCode::UNPROCESSABLE_ENTITY,
-            Json(ApiResponse {
-                status: false,
-                message: "Validasi Gagal".to_string(),
-                data: Some(json!(field_errors)),
-            }),
-        );
+    let keyword = query.q.trim();
+    if keyword.is_empty() {
+        return Err(AppError::invalid_field(
+            "q",
+            &t("validation.keyword_required"),
+        ));
     }
 
-    // Hash Password Dengan Bcrypt
-    let password = match hash(payload.password, 10) {
-        Ok(hashed) => hashed,
-        Err(_) => {
-            return (
-                // kirim response 500 Internal Server Error
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("Gagal mengenkripsi password")),
-            );
+    let limit = query.limit.unwrap_or(SEARCH_LIMIT) as i64;
+    let terms = fulltext_terms(keyword);
+
+    let results = if terms.is_empty() {
+        // kata kunci terlalu pendek untuk FULLTEXT, pakai prefix LIKE
+        let pattern = like_prefix(keyword);
+        let terms = vec![keyword.to_string()];
+
+        sqlx::query!(
+            r#"
+            SELECT id, name, email, created_at, updated_at
+            FROM users
+            WHERE (name LIKE ? OR email LIKE ?) AND deleted_at IS NULL
+            ORDER BY name ASC, id DESC
+            LIMIT ?
+            "#,
+            pattern,
+            pattern,
+            limit
+        )
+        .fetch_all(&db)
+        .await?
+        .into_iter()
+        .map(|user| UserSearchResponse {
+            highlight: highlight(&user.name, &terms).or_else(|| highlight(&user.email, &terms)),
+            score: None,
+            id: user.id,
+            name: user.name,
+            email: user.email,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        })
+        .collect::<Vec<UserSearchResponse>>()
+    } else {
+        // FULLTEXT, diurutkan berdasarkan relevansi
+        let against = boolean_query(&terms);
+        let short = short_terms(keyword);
+
+        let mut select = QueryBuilder::<MySql>::new(
+            "SELECT id, name, email, created_at, updated_at, MATCH(name, email) AGAINST (",
+        );
+        select
+            .push_bind(against.clone())
+            .push(" IN BOOLEAN MODE) AS score FROM users WHERE MATCH(name, email) AGAINST (")
+            .push_bind(against)
+            .push(" IN BOOLEAN MODE) AND deleted_at IS NULL");
+
+        // kata pendek tidak ada di index FULLTEXT, tetap wajib muncul di name atau email
+        for term in &short {
+            let pattern = like_contains(term);
+            select
+                .push(" AND (name LIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR email LIKE ")
+                .push_bind(pattern)
+                .push(")");
         }
+
+        select
+            .push(" ORDER BY score DESC, id DESC LIMIT ")
+            .push_bind(limit);
+
+        let users: Vec<ScoredUser> = select.build_query_as().fetch_all(&db).await?;
+        let terms = [terms, short].concat();
+
+        users
+            .into_iter()
+            .map(|ScoredUser { user, score }| UserSearchResponse {
+                highlight: highlight(&user.name, &terms).or_else(|| highlight(&user.email, &terms)),
+                score: Some(score),
+                id: user.id,
+                name: user.name,
+                email: user.email,
+                created_at: user.created_at,
+                updated_at: user.updated_at,
+            })
+            .collect::<Vec<UserSearchResponse>>()
     };
 
-    // Insert Data User ke Database
-    let result = sqlx::query!(
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            &t("user.search_results"),
+            json!(results),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users",
+    tag = "users",
+    request_body = UserStoreRequest,
+    responses(
+        (status = 201, description = "User berhasil ditambahkan", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 409, description = "Email sudah terdaftar"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn store(
+    Extension(db): Extension<MySqlPool>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<UserStoreRequest>,
+) -> ApiResult {
+    // Hash Password Dengan Bcrypt
+    let password = hash_password(&payload.password, config.auth.bcrypt_cost)?;
+
+    let mut tx = db.begin().await?;
+
+    // Insert Data User ke Database, get id user yang baru saja dibuat
+    let user_id = sqlx::query!(
         "INSERT INTO users (name, email, password) VALUES (?, ?, ?)",
         payload.name,
         payload.email,
         password
     )
-    .execute(&db)
-    .await;
-
-    match result {
-        Ok(result) => {
-            // get id user yang baru saja dibuat
-            let user_id = result.last_insert_id() as i64;
-
-            // Ambil data user berdasarkan id
-            let user = sqlx::query!(
-                r#"
-                SELECT id, name, email, created_at, updated_at
-                FROM users
-                WHERE id = ?
-                "#,
-                user_id
-            )
-            .fetch_one(&db)
-            .await;
-
-            match user {
-                Ok(user) => {
-                    let response = UserResponse {
-                        id: user.id,
-                        name: user.name,
-                        email: user.email,
-                        created_at: user.created_at,
-                        updated_at: user.updated_at,
-                    };
-
-                    (
-                        // kirim response 201 Created
-                        StatusCode::CREATED,
-                        Json(ApiResponse::success(
-                            "User berhasil ditambahkan",
-                            json!(response),
-                        )),
-                    )
-                }
-                Err(_) => (
-                    // kirim response 500 Internal Server Error
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error("Gagal mengambil data user")),
-                ),
-            }
-        }
-        Err(e) => {
-            if e.to_string().contains("Duplicate entry") {
-                (
-                    // kirim response 409 Conflict
-                    StatusCode::CONFLICT,
-                    Json(ApiResponse::error("Email sudah terdaftar")),
-                )
-            } else {
-                (
-                    // kirim response 500 Internal Server Error
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error("Gagal menambahkan user")),
-                )
-            }
-        }
-    }
-}
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::from(e).with_conflict_message("auth.email_taken"))?
+    .last_insert_id() as i64;
 
-pub async fn show(
-    Path(id): Path<i64>,
-    Extension(db): Extension<MySqlPool>,
-) -> (StatusCode, Json<ApiResponse<Value>>) {
-    // Ambil data user berdasarkan ID
-    let user = match sqlx::query!(
+    // berikan role default
+    assign_default_role(&mut *tx, user_id).await?;
+
+    // catat audit log dalam transaksi yang sama
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: USER_CREATED,
+        target_id: user_id,
+        changes: diff(
+            &json!({}),
+            &json!({ "name": payload.name, "email": payload.email }),
+        ),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    // Ambil data user berdasarkan id
+    let user = sqlx::query!(
         r#"
         SELECT id, name, email, created_at, updated_at
         FROM users
         WHERE id = ?
         "#,
-        id
+        user_id
     )
     .fetch_one(&db)
-    .await
-    {
-        Ok(user) => user,
-        Err(sqlx::Error::RowNotFound) => {
-            return (
-                // kirim response 404 Not Found
-                StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("User tidak ditemukan")),
-            );
-        }
-        Err(e) => {
-            eprintln!("Database error: {}", e);
-            return (
-                // kirim response 500 Internal Server Error
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("Gagal mengambil data user")),
-            );
-        }
+    .await?;
+
+    let response = UserResponse {
+        id: user.id,
+        name: user.name,
+        email: user.email,
+        created_at: user.created_at,
+        updated_at: user.updated_at,
     };
 
+    Ok((
+        // kirim response 201 Created
+        StatusCode::CREATED,
+        Json(ApiResponse::success(&t("user.created"), json!(response))),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}",
+    tag = "users",
+    params(("id" = i64, Path, description = "ID user")),
+    responses(
+        (status = 200, description = "Detail user", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 404, description = "Data tidak ditemukan"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn show(Path(id): Path<i64>, Extension(db): Extension<MySqlPool>) -> ApiResult {
+    // Ambil data user berdasarkan ID
+    let user = sqlx::query!(
+        r#"
+        SELECT id, name, email, created_at, updated_at
+        FROM users
+        WHERE id = ? AND deleted_at IS NULL
+        "#,
+        id
+    )
+    .fetch_optional(&db)
+    .await?
+    .ok_or_else(user_not_found)?;
+
     let response = UserResponse {
         id: user.id,
         name: user.name,
@@ -200,126 +510,115 @@ pub async fn show(
         updated_at: user.updated_at,
     };
 
-    (
+    Ok((
         // kirim response 200 OK
         StatusCode::OK,
-        Json(ApiResponse::success("Detail user", json!(response))),
-    )
+        Json(ApiResponse::success(&t("user.detail"), json!(response))),
+    ))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}",
+    tag = "users",
+    params(("id" = i64, Path, description = "ID user")),
+    request_body = UserUpdateRequest,
+    responses(
+        (status = 200, description = "User berhasil diperbarui", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission, atau mengubah email/password sendiri (pakai /api/me)"),
+        (status = 404, description = "Data tidak ditemukan"),
+        (status = 409, description = "Email sudah terdaftar"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
 pub async fn update(
     Path(id): Path<i64>,
     Extension(db): Extension<MySqlPool>,
-    Json(payload): Json<UserUpdateRequest>,
-) -> (StatusCode, Json<ApiResponse<Value>>) {
-    // Validasi dasar (name & email)
-    if let Err(errors) = payload.validate() {
-        let mut field_errors: HashMap<String, Vec<String>> = HashMap::new();
-
-        // kumpulkan semua error dari validasi
-        for (field, errors) in errors.field_errors() {
-            let messages = errors
-                .iter()
-                .filter_map(|e| e.message.as_ref())
-                .map(|m| m.to_string())
-                .collect::<Vec<String>>();
-
-            field_errors.insert(field.to_string(), messages);
-        }
-
-        return (
-            // kirim response 422 Unprocessable Entity
-            StatusCode::UNPROCESSABLE_ENTITY,
-            Json(ApiResponse {
-                status: false,
-                message: "Validasi Gagal".to_string(),
-                data: Some(json!(field_errors)),
-            }),
-        );
-    }
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(mailer): Extension<Arc<dyn MailSender>>,
+    Extension(revocations): Extension<Arc<RevocationCache>>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+    ValidatedJson(payload): ValidatedJson<UserUpdateRequest>,
+) -> ApiResult {
+    // akun sendiri: email & password hanya lewat /api/me, yang meminta password saat ini,
+    // mencabut sesi dan memverifikasi ulang email
+    let is_self = id == claims.sub;
+    let credentials_forbidden = || AppError::Forbidden("user.self_credentials");
 
     // Validasi password opsional
-    if let Some(password) = &payload.password {
-        if !password.is_empty() && password.len() < 6 {
-            let mut errors = HashMap::new();
-            errors.insert(
-                "password".to_string(),
-                vec!["Password minimal 6 karakter".to_string()],
-            );
-
-            return (
-                StatusCode::UNPROCESSABLE_ENTITY,
-                Json(ApiResponse {
-                    status: false,
-                    message: "Validasi Gagal".to_string(),
-                    data: Some(json!(errors)),
-                }),
-            );
+    if let Some(password) = &payload.password
+        && !password.is_empty()
+    {
+        if is_self {
+            return Err(credentials_forbidden());
         }
-    }
 
-    // Cek user exist
-    let user_exist = match sqlx::query!("SELECT id FROM users WHERE id = ?", id)
-        .fetch_one(&db)
-        .await
-    {
-        Ok(user) => user,
-        Err(sqlx::Error::RowNotFound) => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("User tidak ditemukan")),
-            );
+        if password.len() < 6 {
+            return Err(AppError::invalid_field(
+                "password",
+                &t("validation.password_min"),
+            ));
         }
-        Err(_) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("Terjadi kesalahan sistem")),
-            );
+    }
+
+    // Hash password Dengan Bcrypt (bila diisi)
+    let hashed = match &payload.password {
+        Some(password) if !password.is_empty() => {
+            Some(hash_password(password, config.auth.bcrypt_cost)?)
         }
+        _ => None,
     };
 
+    let mut tx = db.begin().await?;
+
+    // Cek user exist, sekaligus data sebelum diubah untuk audit log
+    let user_exist = sqlx::query!(
+        "SELECT id, name, email FROM users WHERE id = ? AND deleted_at IS NULL FOR UPDATE",
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(user_not_found)?;
+
+    let email_changed = payload.email != user_exist.email;
+
+    if is_self && email_changed {
+        return Err(credentials_forbidden());
+    }
+
     // Cek email unique (kecuali diri sendiri)
     let email_exists = sqlx::query!(
-        "SELECT id FROM users WHERE email = ? AND id != ?",
+        "SELECT id FROM users WHERE email = ? AND id != ? AND deleted_at IS NULL",
         payload.email,
         user_exist.id
     )
-    .fetch_optional(&db)
-    .await;
+    .fetch_optional(&mut *tx)
+    .await?;
 
-    if let Ok(Some(_)) = email_exists {
-        return (
-            StatusCode::CONFLICT,
-            Json(ApiResponse::error("Email sudah terdaftar")),
-        );
+    if email_exists.is_some() {
+        return Err(AppError::Conflict("auth.email_taken"));
     }
 
     // Update user
-    let result = match &payload.password {
-        Some(password) if !password.is_empty() => {
-            // Hash password Dengan Bcrypt
-            let hashed = match hash(password, 10) {
-                Ok(h) => h,
-                Err(_) => {
-                    return (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(ApiResponse::error("Gagal mengenkripsi password")),
-                    );
-                }
-            };
-
-            // Update user dengan password
+    match &hashed {
+        Some(hashed) => {
+            // Update user dengan password, semua sesi user diakhiri
             sqlx::query!(
-                "UPDATE users SET name = ?, email = ?, password = ? WHERE id = ?",
+                "UPDATE users SET name = ?, email = ?, password = ?, tokens_revoked_before = NOW() WHERE id = ?",
                 payload.name,
                 payload.email,
                 hashed,
                 id
             )
-            .execute(&db)
+            .execute(&mut *tx)
             .await
         }
-        _ => {
+        None => {
             // Update user tanpa password
             sqlx::query!(
                 "UPDATE users SET name = ?, email = ? WHERE id = ?",
@@ -327,16 +626,60 @@ pub async fn update(
                 payload.email,
                 id
             )
-            .execute(&db)
+            .execute(&mut *tx)
             .await
         }
+    }
+    .map_err(|e| AppError::from(e).with_conflict_message("auth.email_taken"))?;
+
+    if hashed.is_some() {
+        sqlx::query!(
+            "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = ? AND revoked_at IS NULL",
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // email baru harus diverifikasi ulang, link yang dikirim ke email lama tidak berlaku
+    let verification_token = if email_changed {
+        sqlx::query!("UPDATE users SET email_verified_at = NULL WHERE id = ?", id)
+            .execute(&mut *tx)
+            .await?;
+
+        revoke_verification_tokens(&mut *tx, id).await?;
+        Some(issue_verification_token(&mut *tx, id).await?)
+    } else {
+        None
+    };
+
+    // catat audit log, hash password tidak pernah ikut disimpan
+    let mut changes = diff(
+        &json!({ "name": user_exist.name, "email": user_exist.email }),
+        &json!({ "name": payload.name, "email": payload.email }),
+    );
+    changes["password_changed"] = json!(hashed.is_some());
+
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: USER_UPDATED,
+        target_id: id,
+        changes,
+        client: &client,
     };
+    record_audit(&mut *tx, audit).await?;
 
-    if let Err(_) = result {
-        return (
-            // kirim response 500 Internal Server Error
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("Gagal memperbarui data user")),
+    tx.commit().await?;
+
+    if hashed.is_some() {
+        revocations.forget_user(id);
+    }
+
+    if let Some(token) = verification_token {
+        // kirim link verifikasi ke email baru
+        send_in_background(
+            mailer,
+            verification_mail(&config, &payload.name, &payload.email, &token),
         );
     }
 
@@ -345,13 +688,13 @@ pub async fn update(
         r#"
         SELECT id, name, email, created_at, updated_at
         FROM users
-        WHERE id = ?
+        WHERE id = ? AND deleted_at IS NULL
         "#,
         id
     )
-    .fetch_one(&db)
-    .await
-    .unwrap();
+    .fetch_optional(&db)
+    .await?
+    .ok_or_else(user_not_found)?;
 
     let response = UserResponse {
         id: user.id,
@@ -361,61 +704,351 @@ pub async fn update(
         updated_at: user.updated_at,
     };
 
-    (
+    Ok((
         // kirim response 200 OK
         StatusCode::OK,
-        Json(ApiResponse::success(
-            "User berhasil diperbarui",
-            json!(response),
-        )),
-    )
+        Json(ApiResponse::success(&t("user.updated"), json!(response))),
+    ))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}",
+    tag = "users",
+    params(("id" = i64, Path, description = "ID user")),
+    responses(
+        (status = 200, description = "User dipindahkan ke trash"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 404, description = "Data tidak ditemukan"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
 pub async fn destroy(
     Path(id): Path<i64>,
     Extension(db): Extension<MySqlPool>,
-) -> (StatusCode, Json<ApiResponse<Value>>) {
+    Extension(revocations): Extension<Arc<RevocationCache>>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+) -> ApiResult {
     // Cek user exist
-    let user = match sqlx::query!("SELECT id FROM users WHERE id = ?", id)
+    let user = sqlx::query!(
+        "SELECT id FROM users WHERE id = ? AND deleted_at IS NULL",
+        id
+    )
+    .fetch_optional(&db)
+    .await?
+    .ok_or_else(user_not_found)?;
+
+    // Pindahkan user ke trash dan akhiri semua sesinya
+    let mut tx = db.begin().await?;
+
+    sqlx::query!(
+        "UPDATE users SET deleted_at = NOW(), tokens_revoked_before = NOW() WHERE id = ?",
+        user.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = ? AND revoked_at IS NULL",
+        user.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: USER_DELETED,
+        target_id: user.id,
+        changes: json!(null),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    revocations.forget_user(user.id);
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("user.deleted"), json!(null))),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/trash",
+    tag = "users",
+    params(UserTrashQuery),
+    responses(
+        (status = 200, description = "List user di trash dengan pagination", body = ApiResponse<Vec<TrashedUserResponse>>),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 422, description = "Validasi gagal"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn trash(
+    Extension(db): Extension<MySqlPool>,
+    query: Result<Query<UserTrashQuery>, QueryRejection>,
+) -> ApiResult {
+    let Query(query) = query.map_err(invalid_query)?;
+
+    // Validasi Request
+    query.validate()?;
+
+    let per_page = query.per_page.unwrap_or(DEFAULT_PER_PAGE);
+    let page = query.page.unwrap_or(1);
+
+    // Hitung total user di trash
+    let total = sqlx::query_scalar!("SELECT COUNT(*) FROM users WHERE deleted_at IS NOT NULL")
         .fetch_one(&db)
-        .await
-    {
-        Ok(user) => user,
-        Err(sqlx::Error::RowNotFound) => {
-            return (
-                // kirim response 404 Not Found
-                StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("User tidak ditemukan")),
-            );
-        }
-        Err(e) => {
-            eprintln!("Database error: {}", e);
-            return (
-                // kirim response 500 Internal Server Error
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("Terjadi kesalahan sistem")),
-            );
-        }
+        .await?;
+
+    // Ambil user di trash satu halaman, yang terakhir dihapus lebih dulu
+    let users = sqlx::query!(
+        r#"
+        SELECT id, name, email, created_at, updated_at, deleted_at AS `deleted_at!`
+        FROM users
+        WHERE deleted_at IS NOT NULL
+        ORDER BY deleted_at DESC, id DESC
+        LIMIT ? OFFSET ?
+        "#,
+        per_page as i64,
+        (page as i64 - 1) * per_page as i64
+    )
+    .fetch_all(&db)
+    .await?;
+
+    let response = users
+        .into_iter()
+        .map(|user| TrashedUserResponse {
+            id: user.id,
+            name: user.name,
+            email: user.email,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            deleted_at: user.deleted_at,
+        })
+        .collect::<Vec<TrashedUserResponse>>();
+
+    let last = last_page(total, per_page);
+    let meta = PaginationMeta {
+        total,
+        per_page,
+        current_page: Some(page),
+        last_page: Some(last),
+        next_cursor: None,
+        links: PaginationLinks {
+            first: trash_page_link(&query, 1),
+            prev: (page > 1).then(|| trash_page_link(&query, page - 1)),
+            next: (page < last).then(|| trash_page_link(&query, page + 1)),
+            last: Some(trash_page_link(&query, last)),
+        },
     };
 
-    // Hapus user dari database
-    let result = sqlx::query!("DELETE FROM users WHERE id = ?", user.id)
-        .execute(&db)
-        .await;
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::paginated(
+            &t("user.trash_list"),
+            json!(response),
+            meta,
+        )),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/restore",
+    tag = "users",
+    params(("id" = i64, Path, description = "ID user")),
+    responses(
+        (status = 200, description = "User berhasil dikembalikan", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 404, description = "Data tidak ditemukan"),
+        (status = 409, description = "Email sudah dipakai user lain"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn restore(
+    Path(id): Path<i64>,
+    Extension(db): Extension<MySqlPool>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+) -> ApiResult {
+    // Kembalikan user dari trash, dicatat di audit log
+    let mut tx = db.begin().await?;
+
+    let restored = sqlx::query!(
+        "UPDATE users SET deleted_at = NULL WHERE id = ? AND deleted_at IS NOT NULL",
+        id
+    )
+    .execute(&mut *tx)
+    .await
+    // email sudah dipakai user aktif lain
+    .map_err(|e| AppError::from(e).with_conflict_message("auth.email_taken"))?
+    .rows_affected();
+
+    if restored == 0 {
+        return Err(trashed_user_not_found());
+    }
+
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: USER_RESTORED,
+        target_id: id,
+        changes: json!(null),
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    // Ambil data user yang dikembalikan
+    let user = sqlx::query!(
+        r#"
+        SELECT id, name, email, created_at, updated_at
+        FROM users
+        WHERE id = ? AND deleted_at IS NULL
+        "#,
+        id
+    )
+    .fetch_one(&db)
+    .await?;
+
+    let response = UserResponse {
+        id: user.id,
+        name: user.name,
+        email: user.email,
+        created_at: user.created_at,
+        updated_at: user.updated_at,
+    };
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("user.restored"), json!(response))),
+    ))
+}
 
-    match result {
-        Ok(_) => (
-            // kirim response 200 OK
-            StatusCode::OK,
-            Json(ApiResponse::success("User berhasil dihapus", json!(null))),
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}/purge",
+    tag = "users",
+    params(("id" = i64, Path, description = "ID user")),
+    responses(
+        (status = 200, description = "User dihapus permanen"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 404, description = "Data tidak ditemukan"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn purge(
+    Path(id): Path<i64>,
+    Extension(db): Extension<MySqlPool>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+) -> ApiResult {
+    // Hapus permanen, hanya untuk user yang sudah ada di trash
+    let mut tx = db.begin().await?;
+
+    let user = sqlx::query!(
+        "SELECT name, email FROM users WHERE id = ? AND deleted_at IS NOT NULL FOR UPDATE",
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(trashed_user_not_found)?;
+
+    sqlx::query!("DELETE FROM users WHERE id = ?", id)
+        .execute(&mut *tx)
+        .await?;
+
+    // data terakhir disimpan di audit log karena barisnya sudah hilang
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: USER_PURGED,
+        target_id: id,
+        changes: diff(
+            &json!({ "name": user.name, "email": user.email }),
+            &json!({}),
         ),
-        Err(e) => {
-            eprintln!("Database error: {}", e);
-            (
-                // kirim response 500 Internal Server Error
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("Gagal menghapus user")),
-            )
-        }
+        client: &client,
+    };
+    record_audit(&mut *tx, audit).await?;
+
+    tx.commit().await?;
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("user.purged"), json!(null))),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/unlock",
+    tag = "users",
+    params(("id" = i64, Path, description = "ID user")),
+    responses(
+        (status = 200, description = "Kunci login akun dibuka"),
+        (status = 401, description = "Belum login atau token tidak valid"),
+        (status = 403, description = "Tidak memiliki permission"),
+        (status = 404, description = "Data tidak ditemukan"),
+        (status = 429, description = "Terlalu banyak request"),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(skip_all)]
+pub async fn unlock(
+    Path(id): Path<i64>,
+    Extension(db): Extension<MySqlPool>,
+    Extension(throttle): Extension<Arc<LoginThrottle>>,
+    Extension(claims): Extension<Claims>,
+    client: ClientInfo,
+) -> ApiResult {
+    // Cek user exist
+    let user = sqlx::query!(
+        "SELECT id, email FROM users WHERE id = ? AND deleted_at IS NULL",
+        id
+    )
+    .fetch_optional(&db)
+    .await?
+    .ok_or_else(user_not_found)?;
+
+    // hapus hitungan gagal login & kunci milik akun ini
+    throttle.unlock(&user.email).await?;
+
+    let audit = AuditEntry {
+        actor_id: Some(claims.sub),
+        action: USER_UNLOCKED,
+        target_id: user.id,
+        changes: json!({}),
+        client: &client,
+    };
+
+    if let Err(e) = record_audit(&db, audit).await {
+        tracing::error!(error = %e, "Database error");
     }
+
+    Ok((
+        // kirim response 200 OK
+        StatusCode::OK,
+        Json(ApiResponse::success(&t("user.unlocked"), json!(null))),
+    ))
 }
@@ -0,0 +1,121 @@
+use std::future::Future;
+
+// bahasa yang didukung API, default bahasa Indonesia
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    Id,
+    En,
+}
+
+impl Locale {
+    // tag bahasa seperti "en", "en-US" atau "id-ID", hanya subtag utama yang dilihat
+    pub fn parse(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next().unwrap_or_default();
+
+        match primary.to_ascii_lowercase().as_str() {
+            "id" | "in" => Some(Self::Id),
+            "en" => Some(Self::En),
+            _ => None,
+        }
+    }
+
+    // pilih bahasa dari header Accept-Language sesuai bobot q, mis. "en-US,en;q=0.9,id;q=0.8"
+    pub fn negotiate(accept_language: &str) -> Option<Self> {
+        let mut candidates: Vec<(f32, Self)> = accept_language
+            .split(',')
+            .filter_map(|entry| {
+                let mut parts = entry.split(';');
+
+                // "*" berarti bahasa apa saja, dijawab dengan bahasa default
+                let locale = match parts.next()?.trim() {
+                    "*" => Self::default(),
+                    tag => Self::parse(tag)?,
+                };
+
+                let quality = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+
+                (quality > 0.0).then_some((quality, locale))
+            })
+            .collect();
+
+        // sort stabil, urutan di header dipakai bila bobotnya sama
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+        candidates.first().map(|(_, locale)| *locale)
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::En => "en",
+        }
+    }
+}
+
+tokio::task_local! {
+    static CURRENT: Locale;
+}
+
+// jalankan future dengan bahasa tertentu, dipakai middleware locale & auth
+pub async fn scope<F: Future>(locale: Locale, future: F) -> F::Output {
+    CURRENT.scope(locale, future).await
+}
+
+// bahasa request yang sedang diproses, default bila dipanggil di luar request
+pub fn current() -> Locale {
+    CURRENT.try_with(|locale| *locale).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_uses_primary_subtag() {
+        assert_eq!(Locale::parse("id-ID"), Some(Locale::Id));
+        assert_eq!(Locale::parse("in"), Some(Locale::Id));
+        assert_eq!(Locale::parse("en_US"), Some(Locale::En));
+        assert_eq!(Locale::parse(" EN "), Some(Locale::En));
+        assert_eq!(Locale::parse("fr-FR"), None);
+        assert_eq!(Locale::parse(""), None);
+    }
+
+    #[test]
+    fn negotiate_picks_highest_quality() {
+        assert_eq!(
+            Locale::negotiate("en-US,en;q=0.9,id;q=0.8"),
+            Some(Locale::En)
+        );
+        assert_eq!(Locale::negotiate("id;q=0.5, en;q=0.9"), Some(Locale::En));
+        assert_eq!(
+            Locale::negotiate(" en-GB ; q=0.3 , id-ID ;q=0.7"),
+            Some(Locale::Id)
+        );
+    }
+
+    #[test]
+    fn negotiate_keeps_header_order_on_equal_quality() {
+        assert_eq!(Locale::negotiate("en, id"), Some(Locale::En));
+        assert_eq!(Locale::negotiate("id-ID, en"), Some(Locale::Id));
+    }
+
+    #[test]
+    fn negotiate_skips_rejected_and_invalid_entries() {
+        assert_eq!(Locale::negotiate("en;q=0, id"), Some(Locale::Id));
+        assert_eq!(Locale::negotiate("en;q=0.0"), None);
+        assert_eq!(Locale::negotiate("en;q=abc, id;q=0.1"), Some(Locale::Id));
+        assert_eq!(Locale::negotiate("fr, en;q=0.5"), Some(Locale::En));
+    }
+
+    #[test]
+    fn negotiate_answers_wildcard_with_default() {
+        assert_eq!(Locale::negotiate("*"), Some(Locale::Id));
+        assert_eq!(Locale::negotiate("fr, *;q=0.5"), Some(Locale::Id));
+        assert_eq!(Locale::negotiate("*, en;q=0.5"), Some(Locale::Id));
+        assert_eq!(Locale::negotiate("en, *;q=0.5"), Some(Locale::En));
+        assert_eq!(Locale::negotiate("en;q=0, *"), Some(Locale::Id));
+        assert_eq!(Locale::negotiate(""), None);
+    }
+}
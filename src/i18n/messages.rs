@@ -0,0 +1,453 @@
+use crate::i18n::locale::{self, Locale};
+
+// katalog pesan API: (kode, id, en)
+// kode tidak boleh diubah karena ikut dipakai di atribut validator dan bisa dibaca frontend
+const MESSAGES: &[(&str, &str, &str)] = &[
+    // error umum
+    (
+        "error.internal",
+        "Terjadi kesalahan sistem",
+        "An internal error occurred",
+    ),
+    ("error.not_found", "Data tidak ditemukan", "Data not found"),
+    ("error.conflict", "Data sudah ada", "Data already exists"),
+    (
+        "error.invalid_body",
+        "Body request tidak valid",
+        "Invalid request body",
+    ),
+    (
+        "error.invalid_query",
+        "Parameter query tidak valid",
+        "Invalid query parameters",
+    ),
+    (
+        "error.forbidden",
+        "Anda tidak memiliki akses",
+        "You do not have access",
+    ),
+    (
+        "error.too_many_requests",
+        "Terlalu banyak request, coba lagi nanti",
+        "Too many requests, please try again later",
+    ),
+    (
+        "error.password_processing",
+        "Gagal memproses password",
+        "Failed to process password",
+    ),
+    (
+        "error.token_signing",
+        "Gagal membuat token",
+        "Failed to create token",
+    ),
+    // validasi
+    ("validation.failed", "Validasi Gagal", "Validation failed"),
+    (
+        "validation.name_min",
+        "Nama minimal 3 karakter",
+        "Name must be at least 3 characters",
+    ),
+    (
+        "validation.email_invalid",
+        "Email tidak valid",
+        "Invalid email",
+    ),
+    (
+        "validation.password_min",
+        "Password minimal 6 karakter",
+        "Password must be at least 6 characters",
+    ),
+    (
+        "validation.password_required",
+        "Password wajib diisi",
+        "Password is required",
+    ),
+    (
+        "validation.current_password_required",
+        "Password saat ini wajib diisi",
+        "Current password is required",
+    ),
+    (
+        "validation.token_required",
+        "Token wajib diisi",
+        "Token is required",
+    ),
+    (
+        "validation.refresh_token_required",
+        "Refresh token wajib diisi",
+        "Refresh token is required",
+    ),
+    (
+        "validation.mfa_token_required",
+        "Token 2FA wajib diisi",
+        "2FA token is required",
+    ),
+    (
+        "validation.two_factor_code_length",
+        "Kode 2FA harus 6 digit",
+        "2FA code must be 6 digits",
+    ),
+    (
+        "validation.two_factor_code_required",
+        "Kode 2FA atau recovery code wajib diisi",
+        "2FA code or recovery code is required",
+    ),
+    (
+        "validation.page_min",
+        "Halaman minimal 1",
+        "Page must be at least 1",
+    ),
+    (
+        "validation.per_page_range",
+        "per_page harus antara 1 sampai 100",
+        "per_page must be between 1 and 100",
+    ),
+    (
+        "validation.limit_range",
+        "limit harus antara 1 sampai 50",
+        "limit must be between 1 and 50",
+    ),
+    (
+        "validation.keyword_length",
+        "Kata kunci 1 sampai 100 karakter",
+        "Keyword must be 1 to 100 characters",
+    ),
+    (
+        "validation.keyword_required",
+        "Kata kunci wajib diisi",
+        "Keyword is required",
+    ),
+    (
+        "validation.sort_invalid",
+        "Sort hanya boleh id, name, email atau created_at",
+        "Sort must be one of id, name, email or created_at",
+    ),
+    (
+        "validation.cursor_requires_id_sort",
+        "Cursor hanya dapat dipakai dengan sort id",
+        "Cursor can only be used when sorting by id",
+    ),
+    (
+        "validation.page_or_cursor",
+        "Gunakan page atau cursor, tidak keduanya",
+        "Use either page or cursor, not both",
+    ),
+    (
+        "validation.role_name_length",
+        "Nama role 3 sampai 50 karakter",
+        "Role name must be 3 to 50 characters",
+    ),
+    (
+        "validation.description_max",
+        "Deskripsi maksimal 255 karakter",
+        "Description must be at most 255 characters",
+    ),
+    (
+        "validation.locale_unsupported",
+        "Bahasa hanya boleh id atau en",
+        "Language must be id or en",
+    ),
+    (
+        "validation.role_unknown",
+        "Role {name} tidak dikenal",
+        "Unknown role {name}",
+    ),
+    (
+        "validation.permission_unknown",
+        "Permission {name} tidak dikenal",
+        "Unknown permission {name}",
+    ),
+    // autentikasi
+    (
+        "auth.token_missing",
+        "Token tidak ditemukan",
+        "Token not found",
+    ),
+    ("auth.token_invalid", "Token tidak valid", "Invalid token"),
+    (
+        "auth.token_revoked",
+        "Token sudah tidak berlaku",
+        "Token is no longer valid",
+    ),
+    (
+        "auth.invalid_credentials",
+        "Email atau Password Anda Salah",
+        "Incorrect email or password",
+    ),
+    (
+        "auth.email_not_verified",
+        "Email belum diverifikasi, silakan cek email Anda",
+        "Email has not been verified, please check your inbox",
+    ),
+    (
+        "auth.too_many_login_attempts",
+        "Terlalu banyak percobaan login, coba lagi dalam {seconds} detik",
+        "Too many login attempts, try again in {seconds} seconds",
+    ),
+    ("auth.login_success", "Login Berhasil", "Login successful"),
+    (
+        "auth.two_factor_required",
+        "Masukkan kode autentikasi 2FA",
+        "Enter your 2FA authentication code",
+    ),
+    (
+        "auth.register_success",
+        "Register Berhasil!",
+        "Registration successful!",
+    ),
+    (
+        "auth.email_taken",
+        "Email sudah terdaftar",
+        "Email is already registered",
+    ),
+    (
+        "auth.logout_success",
+        "Logout Berhasil",
+        "Logout successful",
+    ),
+    (
+        "auth.logout_all_success",
+        "Logout dari semua perangkat berhasil",
+        "Logged out from all devices",
+    ),
+    // refresh token
+    (
+        "token.refreshed",
+        "Token berhasil diperbarui",
+        "Token refreshed successfully",
+    ),
+    (
+        "token.refresh_invalid",
+        "Refresh token tidak valid",
+        "Invalid refresh token",
+    ),
+    (
+        "token.refresh_expired",
+        "Refresh token sudah kedaluwarsa",
+        "Refresh token has expired",
+    ),
+    (
+        "token.refresh_reused",
+        "Refresh token sudah digunakan, silakan login kembali",
+        "Refresh token has already been used, please log in again",
+    ),
+    // verifikasi email
+    (
+        "email_verification.verified",
+        "Email berhasil diverifikasi",
+        "Email verified successfully",
+    ),
+    (
+        "email_verification.sent",
+        "Jika email terdaftar dan belum diverifikasi, link verifikasi telah dikirim",
+        "If the email is registered and not yet verified, a verification link has been sent",
+    ),
+    (
+        "email_verification.token_invalid",
+        "Token verifikasi tidak valid",
+        "Invalid verification token",
+    ),
+    (
+        "email_verification.token_expired",
+        "Token verifikasi sudah kedaluwarsa, silakan minta link baru",
+        "Verification token has expired, please request a new link",
+    ),
+    // password
+    (
+        "password.reset_link_sent",
+        "Jika email terdaftar, link reset password telah dikirim",
+        "If the email is registered, a password reset link has been sent",
+    ),
+    (
+        "password.reset_token_invalid",
+        "Token reset password tidak valid atau sudah kedaluwarsa",
+        "Password reset token is invalid or has expired",
+    ),
+    (
+        "password.reset_success",
+        "Password berhasil direset, silakan login kembali",
+        "Password has been reset, please log in again",
+    ),
+    (
+        "password.changed",
+        "Password berhasil diganti, silakan login kembali",
+        "Password changed, please log in again",
+    ),
+    (
+        "password.current_incorrect",
+        "Password saat ini salah",
+        "Current password is incorrect",
+    ),
+    ("password.incorrect", "Password salah", "Incorrect password"),
+    // profil
+    ("me.profile", "Profil user", "User profile"),
+    (
+        "me.updated",
+        "Profil berhasil diperbarui",
+        "Profile updated successfully",
+    ),
+    // 2FA
+    (
+        "two_factor.already_enabled",
+        "2FA sudah aktif",
+        "2FA is already enabled",
+    ),
+    (
+        "two_factor.not_enabled",
+        "2FA belum aktif",
+        "2FA is not enabled",
+    ),
+    (
+        "two_factor.setup_required",
+        "Jalankan setup 2FA terlebih dahulu",
+        "Run the 2FA setup first",
+    ),
+    (
+        "two_factor.required_for_admin",
+        "Akun dengan akses admin wajib mengaktifkan 2FA dan login ulang dengan kode 2FA",
+        "Accounts with admin access must enable 2FA and log in again with a 2FA code",
+    ),
+    (
+        "two_factor.setup",
+        "Scan QR code lalu konfirmasi dengan kode dari aplikasi authenticator",
+        "Scan the QR code and confirm with a code from your authenticator app",
+    ),
+    (
+        "two_factor.enabled",
+        "2FA berhasil diaktifkan, simpan recovery code di tempat yang aman",
+        "2FA enabled, store the recovery codes somewhere safe",
+    ),
+    (
+        "two_factor.disabled",
+        "2FA berhasil dinonaktifkan",
+        "2FA disabled",
+    ),
+    (
+        "two_factor.code_invalid",
+        "Kode 2FA salah",
+        "Incorrect 2FA code",
+    ),
+    (
+        "two_factor.token_invalid",
+        "Token 2FA tidak valid atau sudah kedaluwarsa, silakan login kembali",
+        "2FA token is invalid or has expired, please log in again",
+    ),
+    // user
+    ("user.list", "List user", "User list"),
+    (
+        "user.search_results",
+        "Hasil pencarian user",
+        "User search results",
+    ),
+    ("user.detail", "Detail user", "User detail"),
+    (
+        "user.created",
+        "User berhasil ditambahkan",
+        "User created successfully",
+    ),
+    (
+        "user.updated",
+        "User berhasil diperbarui",
+        "User updated successfully",
+    ),
+    (
+        "user.deleted",
+        "User berhasil dihapus",
+        "User deleted successfully",
+    ),
+    ("user.trash_list", "List user di trash", "Trashed user list"),
+    (
+        "user.restored",
+        "User berhasil dikembalikan",
+        "User restored successfully",
+    ),
+    (
+        "user.purged",
+        "User berhasil dihapus permanen",
+        "User permanently deleted",
+    ),
+    (
+        "user.unlocked",
+        "Kunci login akun berhasil dibuka",
+        "Account login lock removed",
+    ),
+    ("user.not_found", "User tidak ditemukan", "User not found"),
+    (
+        "user.self_credentials",
+        "Gunakan /api/me untuk mengubah email dan /api/me/password untuk mengubah password akun sendiri",
+        "Use /api/me to change your own email and /api/me/password to change your own password",
+    ),
+    (
+        "user.not_found_in_trash",
+        "User tidak ditemukan di trash",
+        "User not found in trash",
+    ),
+    // role & permission
+    ("role.list", "List role", "Role list"),
+    ("role.detail", "Detail role", "Role detail"),
+    (
+        "role.created",
+        "Role berhasil ditambahkan",
+        "Role created successfully",
+    ),
+    (
+        "role.updated",
+        "Role berhasil diperbarui",
+        "Role updated successfully",
+    ),
+    (
+        "role.deleted",
+        "Role berhasil dihapus",
+        "Role deleted successfully",
+    ),
+    ("role.not_found", "Role tidak ditemukan", "Role not found"),
+    (
+        "role.name_taken",
+        "Role sudah terdaftar",
+        "Role already exists",
+    ),
+    (
+        "role.built_in",
+        "Role bawaan tidak boleh diganti nama atau dihapus",
+        "Built-in roles cannot be renamed or deleted",
+    ),
+    (
+        "role.admin_permissions_locked",
+        "Permission role admin tidak boleh diubah",
+        "The admin role's permissions cannot be changed",
+    ),
+    ("role.user_roles", "Role user", "User roles"),
+    (
+        "role.user_roles_updated",
+        "Role user berhasil diperbarui",
+        "User roles updated successfully",
+    ),
+    ("permission.list", "List permission", "Permission list"),
+    // audit log
+    ("audit.list", "List audit log", "Audit log list"),
+];
+
+fn lookup(key: &str, locale: Locale) -> Option<&'static str> {
+    MESSAGES
+        .iter()
+        .find(|(code, _, _)| *code == key)
+        .map(|(_, id, en)| match locale {
+            Locale::Id => *id,
+            Locale::En => *en,
+        })
+}
+
+// terjemahkan kode pesan ke bahasa request saat ini, kode dikembalikan apa adanya bila tidak ada di katalog
+pub fn t(key: &str) -> String {
+    lookup(key, locale::current())
+        .map(str::to_string)
+        .unwrap_or_else(|| key.to_string())
+}
+
+// seperti t, dengan placeholder {nama} yang diganti nilainya
+pub fn t_with(key: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(t(key), |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name), value)
+    })
+}
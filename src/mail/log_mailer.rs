@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+
+use crate::mail::mailer::{Mail, MailError, MailSender, SendFuture};
+
+// untuk development: email ditulis ke file (MAIL_LOG_PATH) atau ke stdout
+pub struct LogMailer {
+    path: Option<PathBuf>,
+}
+
+impl LogMailer {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self { path }
+    }
+}
+
+impl MailSender for LogMailer {
+    fn send(&self, mail: Mail) -> SendFuture<'_> {
+        Box::pin(async move {
+            let entry = format!(
+                "To: {}\nSubject: {}\n\n{}\n----------\n",
+                mail.to, mail.subject, mail.body
+            );
+
+            let Some(path) = &self.path else {
+                tracing::info!(to = %mail.to, "{}", entry);
+                return Ok(());
+            };
+
+            if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                tokio::fs::create_dir_all(dir)
+                    .await
+                    .map_err(|e| MailError(e.to_string()))?;
+            }
+
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .await
+                .map_err(|e| MailError(e.to_string()))?;
+
+            file.write_all(entry.as_bytes())
+                .await
+                .map_err(|e| MailError(e.to_string()))
+        })
+    }
+}
@@ -0,0 +1,38 @@
+use std::{fmt, future::Future, pin::Pin, sync::Arc};
+use tracing::Instrument;
+
+// email yang akan dikirim (plain text)
+#[derive(Clone, Debug)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug)]
+pub struct MailError(pub String);
+
+impl fmt::Display for MailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+pub type SendFuture<'a> = Pin<Box<dyn Future<Output = Result<(), MailError>> + Send + 'a>>;
+
+// pengirim email, implementasinya dipilih lewat MAIL_DRIVER (smtp / log)
+pub trait MailSender: Send + Sync {
+    fn send(&self, mail: Mail) -> SendFuture<'_>;
+}
+
+// kirim email tanpa menahan response, kegagalan cukup dicatat (dengan request_id asalnya)
+pub fn send_in_background(mailer: Arc<dyn MailSender>, mail: Mail) {
+    tokio::spawn(
+        async move {
+            if let Err(e) = mailer.send(mail).await {
+                tracing::error!(error = %e, "Mail error");
+            }
+        }
+        .in_current_span(),
+    );
+}
@@ -0,0 +1,4 @@
+pub mod log_mailer;
+pub mod mailer;
+pub mod smtp_mailer;
+pub mod templates;
\ No newline at end of file
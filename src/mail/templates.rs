@@ -0,0 +1,41 @@
+use crate::config::app::AppConfig;
+use crate::mail::mailer::Mail;
+use crate::utils::{
+    email_verification::EMAIL_VERIFICATION_TTL_HOURS, password_reset::PASSWORD_RESET_TTL_MINUTES,
+};
+
+// link verifikasi mengarah ke API (app.url)
+pub fn verification_mail(config: &AppConfig, name: &str, email: &str, token: &str) -> Mail {
+    let link = format!(
+        "{}/api/verify-email?token={}",
+        config.app.url.trim_end_matches('/'),
+        token
+    );
+
+    Mail {
+        to: email.to_string(),
+        subject: "Verifikasi Email Anda".to_string(),
+        body: format!(
+            "Halo {},\n\nSilakan verifikasi email Anda dengan membuka link berikut:\n{}\n\nLink berlaku selama {} jam. Abaikan email ini jika Anda tidak merasa mendaftar.",
+            name, link, EMAIL_VERIFICATION_TTL_HOURS
+        ),
+    }
+}
+
+// halaman reset password ada di frontend (app.frontend_url)
+pub fn password_reset_mail(config: &AppConfig, name: &str, email: &str, token: &str) -> Mail {
+    let link = format!(
+        "{}/reset-password?token={}",
+        config.app.frontend_url.trim_end_matches('/'),
+        token
+    );
+
+    Mail {
+        to: email.to_string(),
+        subject: "Reset Password".to_string(),
+        body: format!(
+            "Halo {},\n\nKami menerima permintaan reset password untuk akun Anda. Buka link berikut untuk membuat password baru:\n{}\n\nLink berlaku selama {} menit dan hanya bisa dipakai satu kali. Abaikan email ini jika Anda tidak meminta reset password.",
+            name, link, PASSWORD_RESET_TTL_MINUTES
+        ),
+    }
+}
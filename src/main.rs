@@ -1,10 +1,16 @@
-use axum::{Extension, Router};
+use axum::{Extension, middleware};
 use dotenvy::dotenv;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 use tower_http::cors::{Any, CorsLayer};
+use utoipa::OpenApi;
+use utoipa_axum::router::OpenApiRouter;
 
 mod config;
 mod handlers;
+mod i18n;
+mod mail;
 mod middlewares;
 mod models;
 mod routes;
@@ -16,29 +22,132 @@ mod utils;
 async fn main() {
     dotenv().ok();
 
-    let db = config::database::connect().await;
+    // konfigurasi dari default, config.toml & env, server berhenti bila ada yang tidak valid
+    let config = config::app::load();
+
+    // log terstruktur (pretty / json) lewat tracing
+    config::logging::init(config.log.format);
+
+    // recorder metrics Prometheus, dipasang sebelum request pertama
+    let metrics = config::metrics::recorder();
+
+    // key JWT & secret token internal
+    utils::jwt_keys::init(config::jwt::keys(&config.jwt));
+    utils::jwt::init(config::jwt::options(&config));
+    utils::signed_token::init(config::jwt::app_key(&config));
+
+    let db = config::database::connect(&config.database).await;
+
+    // cache status pencabutan token, dipakai bersama oleh middleware auth
+    let revocations = Arc::new(utils::revocation::RevocationCache::new());
+
+    // pembatas percobaan login per akun & IP (memory / database)
+    let login_throttle = config::login_throttle::throttle(config.auth.login_throttle_store, &db);
+
+    // pengirim email (smtp / log), dipilih lewat mail.driver
+    let mailer = config::mail::sender(&config.mail);
 
     let cors = CorsLayer::new()
         .allow_origin(Any) // Izinkan semua origin
         .allow_methods(Any) // Izinkan semua method (GET, POST, dll)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers(Any); // supaya frontend bisa membaca RateLimit-* & Retry-After
+
+    // dokumen OpenAPI dikumpulkan dari route yang didaftarkan
+    let (router, openapi) = OpenApiRouter::with_openapi(utils::openapi::ApiDoc::openapi())
+        .merge(routes::auth_routes::auth_routes(&config))
+        .merge(routes::me_routes::me_routes(&config))
+        .merge(routes::user_routes::user_routes(&config))
+        .merge(routes::role_routes::role_routes(&config))
+        .merge(routes::audit_routes::audit_routes(&config))
+        .merge(routes::health_routes::health_routes())
+        .split_for_parts();
+
+    let app = router
+        .merge(routes::well_known_routes::well_known_routes())
+        .merge(routes::docs_routes::docs_routes(openapi))
+        .merge(routes::metrics_routes::metrics_routes(&config, metrics))
+        // jumlah & latency request per route template
+        .layer(middleware::from_fn(middlewares::metrics_middleware::track))
+        .layer(Extension(db.clone()))
+        .layer(Extension(revocations))
+        .layer(Extension(mailer))
+        .layer(Extension(login_throttle))
+        .layer(Extension(config.clone()))
+        // bahasa pesan API dari Accept-Language (id / en)
+        .layer(middleware::from_fn(middlewares::locale_middleware::locale))
+        // error sebagai problem+json (RFC 7807) bila diminta lewat Accept atau ERROR_FORMAT
+        .layer(middleware::from_fn_with_state(
+            config::problem::options(&config.errors),
+            middlewares::problem_middleware::problem,
+        ))
+        .layer(cors)
+        // X-Request-Id & span request, paling luar supaya semua log request ikut tercatat
+        .layer(middleware::from_fn(
+            middlewares::request_id_middleware::request_id,
+        ));
+
+    // SIGTERM / SIGINT: berhenti menerima koneksi baru lalu tunggu request yang sedang berjalan
+    let shutdown = utils::shutdown::Shutdown::listen();
+    let drain_timeout = Duration::from_secs(config.app.shutdown_timeout_seconds);
+
+    let served = match &config.app.socket {
+        // di belakang reverse proxy, IP client diambil dari X-Forwarded-For (TRUST_PROXY)
+        #[cfg(unix)]
+        Some(path) => {
+            use std::os::unix::fs::FileTypeExt;
+
+            // socket sisa proses sebelumnya membuat bind gagal, file lain tidak disentuh
+            if std::fs::metadata(path).is_ok_and(|meta| meta.file_type().is_socket()) {
+                let _ = std::fs::remove_file(path);
+            }
+
+            let listener = match tokio::net::UnixListener::bind(path) {
+                Ok(listener) => listener,
+                Err(err) => {
+                    tracing::error!(error = %err, "Failed to Bind {}", path.display());
+                    std::process::exit(1);
+                }
+            };
+
+            tracing::info!("Server running on unix:{}", path.display());
+
+            let server = axum::serve(listener, app.into_make_service())
+                .with_graceful_shutdown(shutdown.clone().triggered());
+            let served = shutdown.drain(server, drain_timeout).await;
+
+            let _ = std::fs::remove_file(path);
+            served
+        }
+        _ => {
+            let addr = SocketAddr::new(config.app.host, config.app.port);
+
+            let listener = match tokio::net::TcpListener::bind(addr).await {
+                Ok(listener) => listener,
+                Err(err) => {
+                    tracing::error!(error = %err, "Failed to Bind {}", addr);
+                    std::process::exit(1);
+                }
+            };
 
-    let app = Router::new()
-        .merge(routes::auth_routes::auth_routes())
-        .merge(routes::user_routes::user_routes())
-        .layer(Extension(db))
-        .layer(cors);
+            tracing::info!("Server running on http://{}", addr);
 
-    let port = std::env::var("APP_PORT")
-        .ok()
-        .and_then(|p| p.parse::<u16>().ok())
-        .unwrap_or(3001);
+            // connect info dipakai untuk mencatat IP client (audit log)
+            let server = axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(shutdown.clone().triggered());
 
-    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+            shutdown.drain(server, drain_timeout).await
+        }
+    };
 
-    println!("Server running on http://{}", addr);
+    if let Err(err) = served {
+        tracing::error!(error = %err, "Server Error");
+    }
 
-    axum::serve(tokio::net::TcpListener::bind(addr).await.unwrap(), app)
-        .await
-        .unwrap();
+    // pool ditutup setelah server berhenti, koneksi dikembalikan ke MySQL dengan rapi
+    db.close().await;
+    tracing::info!("Database Connection Closed");
 }
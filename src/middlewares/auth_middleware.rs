@@ -2,40 +2,61 @@ use axum::{
     extract::Request, 
     middleware::Next, 
     response::Response,
-    http::{HeaderMap, StatusCode},
-    Json,
+    http::{HeaderMap, HeaderValue, header::CONTENT_LANGUAGE},
+    Extension,
 };
+use sqlx::MySqlPool;
+use std::sync::Arc;
 
+use crate::i18n::locale;
+use crate::utils::app_error::AppError;
 use crate::utils::jwt::verify_token;
-use crate::utils::response::ApiResponse;
-
-type AuthError = (StatusCode, Json<ApiResponse<()>>);
+use crate::utils::permission::load_permissions_and_locale;
+use crate::utils::revocation::RevocationCache;
 
 pub async fn auth(
+    Extension(db): Extension<MySqlPool>,
+    Extension(revocations): Extension<Arc<RevocationCache>>,
     headers: HeaderMap,
     mut req: Request, 
     next: Next
-) -> Result<Response, AuthError> {
+) -> Result<Response, AppError> {
     let token = headers 
         .get(axum::http::header::AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
         .and_then(|v| v.strip_prefix("Bearer "))
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(ApiResponse::<()>::error("Token tidak ditemukan"))
-            )
-        })?;
+        .ok_or_else(|| AppError::Unauthorized("auth.token_missing"))?;
 
     let claims = verify_token(token).map_err(|e| {
-        println!("JWT verification error: {:?}", e);
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(ApiResponse::<()>::error("Token tidak valid"))
-        )
+        tracing::warn!(error = ?e, "JWT verification error");
+        AppError::Unauthorized("auth.token_invalid")
     })?;
 
+    // semua log berikutnya di request ini mencatat user yang login
+    tracing::Span::current().record("user_id", claims.sub);
+
+    // tolak token yang sudah dicabut lewat logout
+    let revoked = revocations.is_revoked(&db, &claims).await?;
+
+    if revoked {
+        return Err(AppError::Unauthorized("auth.token_revoked"));
+    }
+
+    // permission efektif user (dipakai middleware permission & handler) dan bahasa pilihan user,
+    // yang lebih diutamakan daripada header Accept-Language
+    let (permissions, preferred) = load_permissions_and_locale(&db, claims.sub).await?;
+
     req.extensions_mut().insert(claims);
+    req.extensions_mut().insert(permissions);
+
+    let Some(preferred) = preferred else {
+        return Ok(next.run(req).await);
+    };
+
+    let mut response = locale::scope(preferred, next.run(req)).await;
+    response
+        .headers_mut()
+        .insert(CONTENT_LANGUAGE, HeaderValue::from_static(preferred.code()));
 
-    Ok(next.run(req).await)
-}
\ No newline at end of file
+    Ok(response)
+}
@@ -0,0 +1,35 @@
+use axum::{
+    extract::Request,
+    middleware::Next,
+    response::Response,
+    http::{HeaderMap, HeaderValue, header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE, VARY}},
+};
+
+use crate::i18n::locale::{self, Locale};
+
+// pilih bahasa pesan API dari header Accept-Language, dipasang paling luar supaya semua error ikut diterjemahkan
+// bahasa pilihan user yang login diterapkan kemudian oleh middleware auth
+pub async fn locale(
+    headers: HeaderMap,
+    req: Request,
+    next: Next
+) -> Response {
+    let requested = headers
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(Locale::negotiate)
+        .unwrap_or_default();
+
+    let mut response = locale::scope(requested, next.run(req)).await;
+
+    // middleware auth sudah mengisi header ini bila bahasa pilihan user berbeda
+    response
+        .headers_mut()
+        .entry(CONTENT_LANGUAGE)
+        .or_insert(HeaderValue::from_static(requested.code()));
+    response
+        .headers_mut()
+        .append(VARY, HeaderValue::from_static("accept-language"));
+
+    response
+}
@@ -0,0 +1,25 @@
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+
+use crate::utils::metrics::{UNMATCHED_ROUTE, record_request};
+
+// catat jumlah & latency request per route, dipasang lewat Router::layer supaya MatchedPath sudah terisi
+pub async fn track(req: Request, next: Next) -> Response {
+    let method = req.method().clone();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+
+    let started = Instant::now();
+    let response = next.run(req).await;
+
+    record_request(method.as_str(), &route, response.status().as_u16(), started);
+
+    response
+}
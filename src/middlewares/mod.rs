@@ -1 +1,7 @@
-pub mod auth_middleware;
\ No newline at end of file
+pub mod auth_middleware;
+pub mod locale_middleware;
+pub mod metrics_middleware;
+pub mod permission_middleware;
+pub mod problem_middleware;
+pub mod rate_limit_middleware;
+pub mod request_id_middleware;
\ No newline at end of file
@@ -0,0 +1,60 @@
+use axum::{
+    extract::{Path, Request, State},
+    middleware::Next,
+    response::Response,
+    Extension,
+};
+use std::sync::Arc;
+
+use crate::config::app::AppConfig;
+use crate::utils::app_error::AppError;
+use crate::utils::jwt::Claims;
+use crate::utils::permission::PermissionSet;
+
+fn forbidden() -> AppError {
+    AppError::Forbidden("error.forbidden")
+}
+
+// permission admin hanya berlaku untuk sesi yang login lewat 2FA
+fn check_permission(config: &AppConfig, claims: &Claims, permissions: &PermissionSet, permission: &str) -> Result<(), AppError> {
+    if !permissions.has(permission) {
+        return Err(forbidden());
+    }
+
+    if config.auth.require_two_factor_for_admins && !claims.extra.mfa {
+        return Err(AppError::Forbidden("two_factor.required_for_admin"));
+    }
+
+    Ok(())
+}
+
+// hanya user dengan permission tertentu yang boleh lanjut, dipasang setelah middleware auth
+pub async fn require_permission(
+    State(permission): State<&'static str>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(claims): Extension<Claims>,
+    Extension(permissions): Extension<PermissionSet>,
+    req: Request,
+    next: Next
+) -> Result<Response, AppError> {
+    check_permission(&config, &claims, &permissions, permission)?;
+
+    Ok(next.run(req).await)
+}
+
+// seperti require_permission, tapi user selalu boleh mengakses datanya sendiri (/{id})
+pub async fn require_permission_or_self(
+    State(permission): State<&'static str>,
+    Extension(config): Extension<Arc<AppConfig>>,
+    Extension(claims): Extension<Claims>,
+    Extension(permissions): Extension<PermissionSet>,
+    Path(id): Path<i64>,
+    req: Request,
+    next: Next
+) -> Result<Response, AppError> {
+    if claims.sub != id {
+        check_permission(&config, &claims, &permissions, permission)?;
+    }
+
+    Ok(next.run(req).await)
+}
@@ -0,0 +1,32 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+    http::header::ACCEPT,
+};
+use std::sync::Arc;
+
+use crate::utils::problem::{self, ErrorFormat, ProblemContext, ProblemOptions};
+
+// pilih format error per request, AppError dirender sebagai problem+json bila scope ini aktif
+pub async fn problem(
+    State(options): State<Arc<ProblemOptions>>,
+    req: Request,
+    next: Next
+) -> Response {
+    let accept = req
+        .headers()
+        .get(ACCEPT)
+        .and_then(|v| v.to_str().ok());
+
+    if ErrorFormat::negotiate(accept, options.default_format) == ErrorFormat::Envelope {
+        return next.run(req).await;
+    }
+
+    let context = ProblemContext {
+        instance: req.uri().path().to_string(),
+        options,
+    };
+
+    problem::scope(context, next.run(req)).await
+}
@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use crate::utils::app_error::AppError;
+use crate::utils::client_info::ClientInfo;
+use crate::utils::jwt::Claims;
+use crate::utils::rate_limit::{RateLimitDecision, RateLimiter};
+
+fn set_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert("ratelimit-limit", HeaderValue::from(decision.limit));
+    headers.insert("ratelimit-remaining", HeaderValue::from(decision.remaining));
+    headers.insert("ratelimit-reset", HeaderValue::from(decision.reset));
+}
+
+// batasi jumlah request per user (bila sudah login) atau per IP
+// pasang setelah middleware auth supaya Claims sudah tersedia
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    client: ClientInfo,
+    req: Request,
+    next: Next
+) -> Response {
+    // IP dari ClientInfo: X-Forwarded-For dari proxy terpercaya, atau alamat koneksi
+    let key = match (req.extensions().get::<Claims>(), client.ip.as_deref()) {
+        (Some(claims), _) => format!("user:{}", claims.sub),
+        (None, Some(ip)) => format!("ip:{ip}"),
+        // client tanpa IP berbagi satu bucket supaya limit tidak bisa dilewati
+        // hanya terjadi bila proxy tidak mengirim X-Forwarded-For
+        (None, None) => {
+            tracing::warn!("Client IP unknown, using shared rate limit bucket");
+            "ip:unknown".to_string()
+        }
+    };
+
+    let decision = limiter.check(&key);
+
+    if !decision.allowed {
+        let mut response = AppError::TooManyRequests {
+            key: "error.too_many_requests",
+            retry_after: decision.retry_after,
+        }.into_response();
+
+        set_headers(response.headers_mut(), &decision);
+
+        return response;
+    }
+
+    let mut response = next.run(req).await;
+    set_headers(response.headers_mut(), &decision);
+
+    response
+}
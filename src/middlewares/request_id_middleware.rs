@@ -0,0 +1,60 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+use tracing::{Instrument, field};
+
+use crate::utils::refresh_token::random_hex;
+
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+// id dari client dipakai ulang bila formatnya aman untuk log, selain itu dibuat baru
+fn resolve_id(req: &Request) -> String {
+    req.headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|v| v.to_str().ok())
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= 128
+                && id
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b"-_.:".contains(&b))
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| random_hex(16))
+}
+
+// span "request" membungkus semua log selama request diproses, dipasang paling luar
+// user_id diisi middleware auth setelah token diverifikasi
+pub async fn request_id(req: Request, next: Next) -> Response {
+    let id = resolve_id(&req);
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %id,
+        method = %req.method(),
+        path = %req.uri().path(),
+        user_id = field::Empty,
+    );
+
+    let started = Instant::now();
+    let mut response = next.run(req).instrument(span.clone()).await;
+
+    span.in_scope(|| {
+        tracing::info!(
+            status = response.status().as_u16(),
+            latency_ms = started.elapsed().as_millis() as u64,
+            "request completed"
+        )
+    });
+
+    // dikirim balik supaya client bisa mencocokkan laporan error dengan log server
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(X_REQUEST_ID, value);
+    }
+
+    response
+}
@@ -0,0 +1,17 @@
+use serde::Serialize;
+use serde_json::Value;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+
+#[derive(Serialize, sqlx::FromRow, ToSchema)]
+pub struct AuditLog {
+    pub id: i64,
+    pub actor_id: Option<i64>,
+    pub action: String,
+    pub target_id: i64,
+    #[schema(value_type = Option<Object>)]
+    pub changes: Option<Value>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: Option<DateTime<Utc>>
+}
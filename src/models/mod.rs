@@ -1 +1,4 @@
+pub mod audit_log;
+pub mod permission;
+pub mod role;
 pub mod user;
\ No newline at end of file
@@ -0,0 +1,9 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct Permission {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+use chrono::{DateTime, Utc};
+
+#[derive(Serialize)]
+pub struct Role {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>
+}
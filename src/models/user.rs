@@ -1,7 +1,8 @@
 use serde::Serialize;
 use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+#[derive(Serialize, sqlx::FromRow, ToSchema)]
 pub struct User {
     pub id: i64,
     pub name: String, 
@@ -0,0 +1,28 @@
+use axum::middleware;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+// import handler audit log
+use crate::handlers::audit_handler;
+
+// import middleware auth dan permission
+use crate::middlewares::auth_middleware::auth;
+use crate::middlewares::permission_middleware::require_permission;
+
+// import rate limiter per grup route
+use crate::config::{app::AppConfig, rate_limit::limiter};
+use crate::middlewares::rate_limit_middleware::rate_limit;
+
+// import nama permission
+use crate::utils::permission::AUDIT_READ;
+
+pub fn audit_routes(config: &AppConfig) -> OpenApiRouter {
+    OpenApiRouter::new()
+        // GET /api/audit-logs → list audit log perubahan user
+        .routes(routes!(audit_handler::index))
+        // WAJIB punya permission audit.read
+        .layer(middleware::from_fn_with_state(AUDIT_READ, require_permission))
+        // batasi jumlah request per user yang login
+        .layer(middleware::from_fn_with_state(limiter(config, "audit"), rate_limit))
+        // dan WAJIB login
+        .layer(middleware::from_fn(auth))
+}
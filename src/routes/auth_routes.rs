@@ -1,10 +1,36 @@
-use axum::{Router, routing::post};
+use axum::middleware;
+use utoipa_axum::{router::OpenApiRouter, routes};
 
-use crate::handlers::register_handler::register;
-use crate::handlers::login_handler::login;
+// routes! butuh path modul supaya item __path_* dari #[utoipa::path] ikut ditemukan
+use crate::handlers::{
+    email_verification_handler, login_handler, logout_handler, password_handler,
+    register_handler, token_handler, two_factor_handler,
+};
 
-pub fn auth_routes() -> Router {
-    Router::new()
-    .route("/api/register", post(register))
-    .route("/api/login", post(login))
-}
\ No newline at end of file
+// import middleware auth
+use crate::middlewares::auth_middleware::auth;
+
+// import rate limiter per grup route
+use crate::config::{app::AppConfig, rate_limit::limiter};
+use crate::middlewares::rate_limit_middleware::rate_limit;
+
+pub fn auth_routes(config: &AppConfig) -> OpenApiRouter {
+    // logout WAJIB login
+    let protected = OpenApiRouter::new()
+        .routes(routes!(logout_handler::logout))
+        .routes(routes!(logout_handler::logout_all))
+        .layer(middleware::from_fn(auth));
+
+    OpenApiRouter::new()
+    .routes(routes!(register_handler::register))
+    .routes(routes!(login_handler::login))
+    .routes(routes!(two_factor_handler::login))
+    .routes(routes!(token_handler::refresh))
+    .routes(routes!(email_verification_handler::verify))
+    .routes(routes!(email_verification_handler::resend))
+    .routes(routes!(password_handler::forgot))
+    .routes(routes!(password_handler::reset))
+    .merge(protected)
+    // batasi jumlah request per IP, mencegah flood register & login
+    .layer(middleware::from_fn_with_state(limiter(config, "auth"), rate_limit))
+}
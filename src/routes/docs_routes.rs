@@ -0,0 +1,27 @@
+use axum::{Extension, Router, routing::get};
+use std::sync::Arc;
+use utoipa::openapi::OpenApi;
+
+// import handler dokumen OpenAPI
+use crate::handlers::openapi_handler::openapi;
+
+pub fn docs_routes(spec: OpenApi) -> Router {
+    // dirender sekali di sini, dokumen tidak berubah selama server berjalan
+    let json = spec
+        .to_pretty_json()
+        .expect("Gagal membuat dokumen OpenAPI");
+
+    // GET /api/openapi.json → dokumen OpenAPI 3, tanpa login
+    let router = Router::new()
+        .route("/api/openapi.json", get(openapi))
+        .layer(Extension(Arc::new(json)));
+
+    // GET /api/docs → Swagger UI, hanya ikut di-build dengan feature swagger-ui
+    #[cfg(feature = "swagger-ui")]
+    let router = router.merge(
+        utoipa_swagger_ui::SwaggerUi::new("/api/docs")
+            .config(utoipa_swagger_ui::Config::from("/api/openapi.json")),
+    );
+
+    router
+}
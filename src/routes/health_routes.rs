@@ -0,0 +1,13 @@
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+// import handler health check
+use crate::handlers::health_handler;
+
+pub fn health_routes() -> OpenApiRouter {
+    // probe orchestrator, tanpa login & rate limit
+    OpenApiRouter::new()
+        // GET /health/live → proses masih hidup
+        .routes(routes!(health_handler::live))
+        // GET /health/ready → database & migration siap, 503 bila tidak
+        .routes(routes!(health_handler::ready))
+}
@@ -0,0 +1,33 @@
+use axum::middleware;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+// import handler profil user yang sedang login
+// routes! butuh path modul supaya item __path_* dari #[utoipa::path] ikut ditemukan
+use crate::handlers::{me_handler, two_factor_handler};
+
+// import middleware auth
+use crate::middlewares::auth_middleware::auth;
+
+// import rate limiter per grup route
+use crate::config::{app::AppConfig, rate_limit::limiter};
+use crate::middlewares::rate_limit_middleware::rate_limit;
+
+pub fn me_routes(config: &AppConfig) -> OpenApiRouter {
+    OpenApiRouter::new()
+        // GET /api/me → profil user yang sedang login
+        // PATCH /api/me → ubah nama/email/bahasa sendiri
+        .routes(routes!(me_handler::show, me_handler::update))
+        // POST /api/me/password → ganti password, wajib password saat ini
+        .routes(routes!(me_handler::change_password))
+        // 2FA: setup → otpauth URI, confirm → aktif + recovery code, DELETE → nonaktif
+        .routes(routes!(two_factor_handler::disable))
+        .routes(routes!(two_factor_handler::setup))
+        .routes(routes!(two_factor_handler::confirm))
+        // batasi jumlah request per user yang login
+        .layer(middleware::from_fn_with_state(
+            limiter(config, "me"),
+            rate_limit,
+        ))
+        // Semua route di atas WAJIB login
+        .layer(middleware::from_fn(auth))
+}
@@ -0,0 +1,21 @@
+use axum::{Extension, Router, routing::get};
+use metrics_exporter_prometheus::PrometheusHandle;
+
+// import konfigurasi aplikasi
+use crate::config::app::AppConfig;
+
+// import handler metrics
+use crate::handlers::metrics_handler::metrics;
+
+pub fn metrics_routes(config: &AppConfig, handle: PrometheusHandle) -> Router {
+    // tanpa metrics.token endpoint tidak dipasang, data traffic tidak terbuka ke publik
+    if config.metrics.token.is_none() {
+        tracing::info!("METRICS_TOKEN not set, /metrics disabled");
+        return Router::new();
+    }
+
+    // GET /metrics → di-scrape Prometheus dengan header Authorization: Bearer <metrics.token>
+    Router::new()
+        .route("/metrics", get(metrics))
+        .layer(Extension(handle))
+}
@@ -1,2 +1,9 @@
+pub mod audit_routes;
 pub mod auth_routes;
-pub mod user_routes;
\ No newline at end of file
+pub mod docs_routes;
+pub mod health_routes;
+pub mod me_routes;
+pub mod metrics_routes;
+pub mod role_routes;
+pub mod user_routes;
+pub mod well_known_routes;
\ No newline at end of file
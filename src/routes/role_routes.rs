@@ -0,0 +1,34 @@
+use axum::middleware;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+// import handler role
+// routes! butuh path modul supaya item __path_* dari #[utoipa::path] ikut ditemukan
+use crate::handlers::role_handler;
+
+// import middleware auth dan permission
+use crate::middlewares::auth_middleware::auth;
+use crate::middlewares::permission_middleware::require_permission;
+
+// import rate limiter per grup route
+use crate::config::{app::AppConfig, rate_limit::limiter};
+use crate::middlewares::rate_limit_middleware::rate_limit;
+
+// import nama permission
+use crate::utils::permission::ROLES_MANAGE;
+
+pub fn role_routes(config: &AppConfig) -> OpenApiRouter {
+    OpenApiRouter::new()
+        // GET /api/roles → list role, POST /api/roles → tambah role
+        .routes(routes!(role_handler::index, role_handler::store))
+        .routes(routes!(role_handler::show, role_handler::update, role_handler::destroy))
+        // GET /api/permissions → list permission yang tersedia
+        .routes(routes!(role_handler::permissions))
+        // role milik user
+        .routes(routes!(role_handler::user_roles, role_handler::assign_user_roles))
+        // Semua route di atas WAJIB punya permission roles.manage
+        .layer(middleware::from_fn_with_state(ROLES_MANAGE, require_permission))
+        // batasi jumlah request per user yang login
+        .layer(middleware::from_fn_with_state(limiter(config, "roles"), rate_limit))
+        // dan WAJIB login
+        .layer(middleware::from_fn(auth))
+}
@@ -1,23 +1,65 @@
-use axum::{
-    Router, middleware,
-    routing::{delete, get, post, put},
+use axum::middleware;
+use utoipa_axum::{
+    router::{OpenApiRouter, UtoipaMethodRouterExt},
+    routes,
 };
 
 // import handler user
-use crate::handlers::user_handler::{destroy, index, show, store, update};
+// routes! butuh path modul supaya item __path_* dari #[utoipa::path] ikut ditemukan
+use crate::handlers::user_handler;
 
-// import middleware auth
+// import middleware auth dan permission
 use crate::middlewares::auth_middleware::auth;
+use crate::middlewares::permission_middleware::{require_permission, require_permission_or_self};
 
-pub fn user_routes() -> Router {
-    Router::new()
+// import rate limiter per grup route
+use crate::config::{app::AppConfig, rate_limit::limiter};
+use crate::middlewares::rate_limit_middleware::rate_limit;
+
+// import nama permission
+use crate::utils::permission::{USERS_DELETE, USERS_PURGE, USERS_READ, USERS_WRITE};
+
+pub fn user_routes(config: &AppConfig) -> OpenApiRouter {
+    OpenApiRouter::new()
         // GET /api/users → list semua user
-        .route("/api/users", get(index))
+        .routes(
+            routes!(user_handler::index).layer(middleware::from_fn_with_state(USERS_READ, require_permission)),
+        )
         // POST /api/users → tambah user
-        .route("/api/users", post(store))
-        .route("/api/users/{id}", get(show))
-        .route("/api/users/{id}", put(update))
-        .route("/api/users/{id}", delete(destroy))
+        .routes(
+            routes!(user_handler::store).layer(middleware::from_fn_with_state(USERS_WRITE, require_permission)),
+        )
+        // GET /api/users/search?q= → cari user berdasarkan nama/email
+        .routes(
+            routes!(user_handler::search).layer(middleware::from_fn_with_state(USERS_READ, require_permission)),
+        )
+        // user selalu boleh melihat & mengubah datanya sendiri
+        .routes(
+            routes!(user_handler::show).layer(middleware::from_fn_with_state(USERS_READ, require_permission_or_self)),
+        )
+        .routes(
+            routes!(user_handler::update).layer(middleware::from_fn_with_state(USERS_WRITE, require_permission_or_self)),
+        )
+        .routes(
+            routes!(user_handler::destroy).layer(middleware::from_fn_with_state(USERS_DELETE, require_permission)),
+        )
+        // trash: user yang sudah dihapus (soft delete)
+        .routes(
+            routes!(user_handler::trash).layer(middleware::from_fn_with_state(USERS_DELETE, require_permission)),
+        )
+        .routes(
+            routes!(user_handler::restore).layer(middleware::from_fn_with_state(USERS_DELETE, require_permission)),
+        )
+        // hapus permanen (admin)
+        .routes(
+            routes!(user_handler::purge).layer(middleware::from_fn_with_state(USERS_PURGE, require_permission)),
+        )
+        // buka kunci akun yang terkunci karena terlalu banyak gagal login
+        .routes(
+            routes!(user_handler::unlock).layer(middleware::from_fn_with_state(USERS_WRITE, require_permission)),
+        )
+        // batasi jumlah request per user yang login
+        .layer(middleware::from_fn_with_state(limiter(config, "users"), rate_limit))
         // Semua route di atas WAJIB login
         .layer(middleware::from_fn(auth))
 }
@@ -0,0 +1,9 @@
+use axum::{Router, routing::get};
+
+// import handler JWKS
+use crate::handlers::jwks_handler::jwks;
+
+pub fn well_known_routes() -> Router {
+    // GET /.well-known/jwks.json → public key JWT, tanpa login
+    Router::new().route("/.well-known/jwks.json", get(jwks))
+}
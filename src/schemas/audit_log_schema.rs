@@ -0,0 +1,22 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+use validator::Validate;
+
+#[derive(Deserialize, Serialize, Validate, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditLogQuery {
+    #[validate(range(min = 1, code = "validation.page_min"))]
+    #[param(minimum = 1)]
+    pub page: Option<u32>,
+
+    #[validate(range(min = 1, max = 100, code = "validation.per_page_range"))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u32>,
+
+    pub actor_id: Option<i64>,
+    pub target_id: Option<i64>,
+    pub action: Option<String>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VerifyEmailQuery {
+    pub token: String,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ResendVerificationRequest {
+    #[validate(email(code = "validation.email_invalid"))]
+    #[schema(format = "email")]
+    pub email: String,
+}
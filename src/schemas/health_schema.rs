@@ -0,0 +1,36 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LivenessResponse {
+    pub status: HealthStatus,
+}
+
+// status satu dependency, detail error hanya dicatat di log
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ComponentHealth {
+    pub status: HealthStatus,
+    // komponen required yang down membuat service tidak ready
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    // jumlah migration yang belum dijalankan
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    pub status: HealthStatus,
+    pub checks: BTreeMap<String, ComponentHealth>,
+}
@@ -1,24 +1,52 @@
 use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct LoginRequest{
-    #[validate(email(message = "Email tidak valid"))]
+    #[validate(email(code = "validation.email_invalid"))]
+    #[schema(format = "email")]
     pub email: String, 
 
-    #[validate(length(min = 6, message = "Password minimal 6 karakter"))]
+    #[validate(length(min = 6, code = "validation.password_min"))]
+    #[schema(min_length = 6, format = "password")]
     pub password: String,   
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+// nama schema dibedakan dari user_schema::UserResponse di dokumen OpenAPI
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[schema(as = LoginUserResponse)]
 pub struct UserResponse{
     pub id: i64,
     pub name: String, 
-    pub email: String 
+    pub email: String,
+    pub roles: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct LoginResponse{
     pub user: UserResponse,
-    pub token: String 
-}
\ No newline at end of file
+    pub token: String,
+    pub refresh_token: String,
+    // user punya permission admin tapi login tanpa 2FA, permission admin belum berlaku sampai 2FA diaktifkan
+    pub mfa_setup_required: bool,
+}
+
+// dikirim saat login bila 2FA aktif, mfa_token ditukar di /api/login/2fa
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MfaPendingResponse{
+    pub mfa_required: bool,
+    pub mfa_token: String,
+}
+
+// isi salah satu: code dari aplikasi authenticator atau recovery_code
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct TwoFactorLoginRequest{
+    #[validate(length(min = 1, code = "validation.mfa_token_required"))]
+    #[schema(min_length = 1)]
+    pub mfa_token: String,
+
+    pub code: Option<String>,
+
+    pub recovery_code: Option<String>,
+}
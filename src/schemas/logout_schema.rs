@@ -0,0 +1,8 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema)]
+pub struct LogoutRequest {
+    // refresh token milik sesi ini, ikut dicabut bila dikirim
+    pub refresh_token: Option<String>,
+}
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+use crate::i18n::locale::Locale;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MeResponse {
+    pub id: i64,
+    pub name: String,
+    pub email: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub two_factor_enabled: bool,
+    pub roles: Vec<String>,
+    pub permissions: Vec<String>,
+    // bahasa pesan API pilihan user, null berarti ikut Accept-Language
+    pub locale: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+// field yang tidak dikirim tidak diubah
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct MeUpdateRequest {
+    #[validate(length(min = 3, code = "validation.name_min"))]
+    #[schema(min_length = 3)]
+    pub name: Option<String>,
+
+    #[validate(email(code = "validation.email_invalid"))]
+    #[schema(format = "email")]
+    pub email: Option<String>,
+
+    #[validate(custom(function = "validate_locale"))]
+    #[schema(example = "en")]
+    pub locale: Option<String>,
+
+    // wajib bila email diganti
+    #[schema(format = "password")]
+    pub current_password: Option<String>,
+}
+
+fn validate_locale(locale: &str) -> Result<(), ValidationError> {
+    match Locale::parse(locale) {
+        Some(_) => Ok(()),
+        None => Err(ValidationError::new("validation.locale_unsupported")),
+    }
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 1, code = "validation.current_password_required"))]
+    #[schema(min_length = 1, format = "password")]
+    pub current_password: String,
+
+    #[validate(length(min = 6, code = "validation.password_min"))]
+    #[schema(min_length = 6, format = "password")]
+    pub password: String,
+}
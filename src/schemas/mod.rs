@@ -1,3 +1,12 @@
+pub mod audit_log_schema;
+pub mod email_verification_schema;
+pub mod health_schema;
 pub mod register_schema;
 pub mod login_schema;
+pub mod logout_schema;
+pub mod me_schema;
+pub mod password_schema;
+pub mod role_schema;
+pub mod token_schema;
+pub mod two_factor_schema;
 pub mod user_schema;
\ No newline at end of file
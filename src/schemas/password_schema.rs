@@ -0,0 +1,21 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ForgotPasswordRequest {
+    #[validate(email(code = "validation.email_invalid"))]
+    #[schema(format = "email")]
+    pub email: String,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ResetPasswordRequest {
+    #[validate(length(min = 1, code = "validation.token_required"))]
+    #[schema(min_length = 1)]
+    pub token: String,
+
+    #[validate(length(min = 6, code = "validation.password_min"))]
+    #[schema(min_length = 6, format = "password")]
+    pub password: String,
+}
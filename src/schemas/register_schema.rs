@@ -1,20 +1,24 @@
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
 use validator::Validate;
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct RegisterRequest {
-    #[validate(length(min = 3, message = "Nama minimal 3 karakter"))]
+    #[validate(length(min = 3, code = "validation.name_min"))]
+    #[schema(min_length = 3)]
     pub name: String, 
 
-    #[validate(email(message = "Email tidak valid"))]
+    #[validate(email(code = "validation.email_invalid"))]
+    #[schema(format = "email")]
     pub email: String, 
 
-    #[validate(length(min = 6, message = "Password minimal 6 karakter"))]
+    #[validate(length(min = 6, code = "validation.password_min"))]
+    #[schema(min_length = 6, format = "password")]
     pub password: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RegisterResponse {
     pub id: i64, 
     pub name: String, 
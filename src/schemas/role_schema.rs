@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct RoleStoreRequest {
+    #[validate(length(min = 3, max = 50, code = "validation.role_name_length"))]
+    #[schema(min_length = 3, max_length = 50)]
+    pub name: String,
+
+    #[validate(length(max = 255, code = "validation.description_max"))]
+    #[schema(max_length = 255)]
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct RoleUpdateRequest {
+    #[validate(length(min = 3, max = 50, code = "validation.role_name_length"))]
+    #[schema(min_length = 3, max_length = 50)]
+    pub name: String,
+
+    #[validate(length(max = 255, code = "validation.description_max"))]
+    #[schema(max_length = 255)]
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RoleResponse {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct UserRolesRequest {
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserRolesResponse {
+    pub user_id: i64,
+    pub roles: Vec<String>,
+}
@@ -0,0 +1,16 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct RefreshTokenRequest {
+    #[validate(length(min = 1, code = "validation.refresh_token_required"))]
+    #[schema(min_length = 1)]
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TokenResponse {
+    pub token: String,
+    pub refresh_token: String,
+}
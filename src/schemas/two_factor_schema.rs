@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TwoFactorSetupResponse {
+    pub secret: String,
+    // ditampilkan sebagai QR code oleh frontend
+    pub otpauth_uri: String,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct TwoFactorConfirmRequest {
+    #[validate(length(equal = 6, code = "validation.two_factor_code_length"))]
+    #[schema(min_length = 6, max_length = 6)]
+    pub code: String,
+}
+
+// recovery code hanya ditampilkan satu kali
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct TwoFactorDisableRequest {
+    #[validate(length(min = 1, code = "validation.password_required"))]
+    #[schema(min_length = 1, format = "password")]
+    pub password: String,
+}
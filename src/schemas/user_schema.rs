@@ -1,20 +1,24 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct UserStoreRequest {
-    #[validate(length(min = 3, message = "Nama minimal 3 karakter"))]
+    #[validate(length(min = 3, code = "validation.name_min"))]
+    #[schema(min_length = 3)]
     pub name: String,
 
-    #[validate(email(message = "Email tidak valid"))]
+    #[validate(email(code = "validation.email_invalid"))]
+    #[schema(format = "email")]
     pub email: String,
 
-    #[validate(length(min = 6, message = "Password minimal 6 karakter"))]
+    #[validate(length(min = 6, code = "validation.password_min"))]
+    #[schema(min_length = 6, format = "password")]
     pub password: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UserResponse {
     pub id: i64,
     pub name: String,
@@ -23,13 +27,87 @@ pub struct UserResponse {
     pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct UserUpdateRequest {
-    #[validate(length(min = 3, message = "Nama minimal 3 karakter"))]
+    #[validate(length(min = 3, code = "validation.name_min"))]
+    #[schema(min_length = 3)]
     pub name: String,
 
-    #[validate(email(message = "Email tidak valid"))]
+    #[validate(email(code = "validation.email_invalid"))]
+    #[schema(format = "email")]
     pub email: String,
 
+    // kosong atau tidak dikirim berarti password tidak diubah
+    #[schema(min_length = 6, format = "password")]
     pub password: Option<String>,
 }
+
+#[derive(Deserialize, Serialize, Validate, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserIndexQuery {
+    #[validate(range(min = 1, code = "validation.page_min"))]
+    #[param(minimum = 1)]
+    pub page: Option<u32>,
+
+    #[validate(range(min = 1, max = 100, code = "validation.per_page_range"))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u32>,
+
+    // id terakhir dari halaman sebelumnya (keyset pagination)
+    pub cursor: Option<i64>,
+
+    // id, name, email, created_at; awali dengan "-" untuk urutan menurun
+    pub sort: Option<String>,
+
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub created_from: Option<NaiveDate>,
+    pub created_to: Option<NaiveDate>,
+}
+
+#[derive(Deserialize, Serialize, Validate, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserTrashQuery {
+    #[validate(range(min = 1, code = "validation.page_min"))]
+    #[param(minimum = 1)]
+    pub page: Option<u32>,
+
+    #[validate(range(min = 1, max = 100, code = "validation.per_page_range"))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u32>,
+}
+
+#[derive(Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserSearchQuery {
+    #[validate(length(min = 1, max = 100, code = "validation.keyword_length"))]
+    #[param(min_length = 1, max_length = 100)]
+    pub q: String,
+
+    #[validate(range(min = 1, max = 50, code = "validation.limit_range"))]
+    #[param(minimum = 1, maximum = 50)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserSearchResponse {
+    pub id: i64,
+    pub name: String,
+    pub email: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    // relevansi FULLTEXT, kosong bila memakai pencarian prefix
+    pub score: Option<f64>,
+    // name/email dengan bagian yang cocok ditandai <mark>
+    pub highlight: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TrashedUserResponse {
+    pub id: i64,
+    pub name: String,
+    pub email: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub deleted_at: DateTime<Utc>,
+}
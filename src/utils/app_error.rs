@@ -0,0 +1,187 @@
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header::RETRY_AFTER},
+    response::{IntoResponse, Response},
+};
+use jsonwebtoken::errors::{Error as JwtError, ErrorKind as JwtErrorKind};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use validator::ValidationErrors;
+
+use crate::i18n::messages::{t, t_with};
+use crate::utils::{
+    problem::{self, ProblemDetails},
+    response::ApiResponse,
+};
+
+// hasil handler: response sukses atau AppError, supaya handler bisa memakai `?`
+pub type ApiResult = Result<(StatusCode, Json<ApiResponse<Value>>), AppError>;
+
+// semua error yang dikirim ke client, variant menentukan status HTTP
+// setiap variant membawa kode pesan i18n, mis. "auth.token_revoked", yang juga dikirim sebagai code
+// supaya client bisa membedakan error tanpa membaca pesan terjemahan, code tidak boleh diubah
+#[derive(Debug)]
+pub enum AppError {
+    // 422, error per field
+    Validation(HashMap<String, Vec<String>>),
+    // 400, body JSON tidak bisa dibaca
+    InvalidBody(&'static str),
+    BadRequest(&'static str),
+    Unauthorized(&'static str),
+    Forbidden(&'static str),
+    NotFound(&'static str),
+    Conflict(&'static str),
+    // pesan boleh memakai placeholder {seconds}
+    TooManyRequests { key: &'static str, retry_after: u64 },
+    // 500, detail hanya dicatat di log
+    Database(sqlx::Error),
+    Internal(&'static str),
+}
+
+impl AppError {
+    // 422 dengan satu error pada field tertentu
+    pub fn invalid_field(field: &str, message: &str) -> Self {
+        let mut errors = HashMap::new();
+        errors.insert(field.to_string(), vec![message.to_string()]);
+        Self::Validation(errors)
+    }
+
+    // ganti kode 409 bawaan dari unique constraint, mis. "auth.email_taken"
+    pub fn with_conflict_message(self, key: &'static str) -> Self {
+        match self {
+            Self::Conflict(_) => Self::Conflict(key),
+            other => other,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::InvalidBody(_) | Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::Database(_) | Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    // kode i18n error, dikirim sebagai code di envelope maupun problem+json
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Validation(_) => "validation.failed",
+            Self::Database(_) => "error.internal",
+            Self::InvalidBody(key)
+            | Self::BadRequest(key)
+            | Self::Unauthorized(key)
+            | Self::Forbidden(key)
+            | Self::NotFound(key)
+            | Self::Conflict(key)
+            | Self::TooManyRequests { key, .. }
+            | Self::Internal(key) => key,
+        }
+    }
+
+    // pesan diterjemahkan saat response dibuat, mengikuti bahasa request
+    pub fn message(&self) -> String {
+        match self {
+            Self::TooManyRequests { key, retry_after } => {
+                t_with(key, &[("seconds", &retry_after.to_string())])
+            }
+            _ => t(self.code()),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        if let Self::Database(e) = &self {
+            tracing::error!(error = %e, "Database error");
+        }
+
+        let data = match &self {
+            Self::Validation(errors) => Some(json!(errors)),
+            _ => None,
+        };
+
+        // problem+json bila diminta client atau diatur global, selain itu envelope ApiResponse
+        let mut response = match problem::current() {
+            Some(context) => {
+                ProblemDetails::new(&context, self.status(), self.code(), self.message(), data)
+                    .into_response()
+            }
+            None => {
+                let body = ApiResponse {
+                    code: Some(self.code()),
+                    data,
+                    ..ApiResponse::error(&self.message())
+                };
+
+                (self.status(), Json(body)).into_response()
+            }
+        };
+
+        if let Self::TooManyRequests { retry_after, .. } = self {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+
+        response
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(e: sqlx::Error) -> Self {
+        match e {
+            sqlx::Error::RowNotFound => Self::NotFound("error.not_found"),
+            e if e
+                .as_database_error()
+                .is_some_and(|db| db.is_unique_violation()) =>
+            {
+                Self::Conflict("error.conflict")
+            }
+            e => Self::Database(e),
+        }
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut field_errors: HashMap<String, Vec<String>> = HashMap::new();
+
+        // kumpulkan semua error dari validasi, code di atribut validator adalah kode pesan i18n
+        for (field, errors) in errors.field_errors() {
+            let messages = errors.iter().map(|e| t(&e.code)).collect::<Vec<String>>();
+
+            field_errors.insert(field.to_string(), messages);
+        }
+
+        Self::Validation(field_errors)
+    }
+}
+
+impl From<bcrypt::BcryptError> for AppError {
+    fn from(e: bcrypt::BcryptError) -> Self {
+        tracing::error!(error = %e, "Bcrypt error");
+        Self::Internal("error.password_processing")
+    }
+}
+
+impl From<JwtError> for AppError {
+    fn from(e: JwtError) -> Self {
+        match e.kind() {
+            // error key / signing berarti konfigurasi server yang salah, bukan token client
+            JwtErrorKind::InvalidEcdsaKey
+            | JwtErrorKind::InvalidEddsaKey
+            | JwtErrorKind::InvalidRsaKey(_)
+            | JwtErrorKind::RsaFailedSigning
+            | JwtErrorKind::InvalidKeyFormat => {
+                tracing::error!(error = ?e, "JWT error");
+                Self::Internal("error.token_signing")
+            }
+            _ => Self::Unauthorized("auth.token_invalid"),
+        }
+    }
+}
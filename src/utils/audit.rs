@@ -0,0 +1,157 @@
+use serde_json::{Map, Value, json};
+use sqlx::{Executor, MySql};
+
+use crate::utils::client_info::ClientInfo;
+
+// nama aksi yang dicatat di audit log
+pub const USER_REGISTERED: &str = "user.registered";
+pub const USER_CREATED: &str = "user.created";
+pub const USER_EMAIL_VERIFIED: &str = "user.email_verified";
+pub const USER_UPDATED: &str = "user.updated";
+pub const USER_PASSWORD_CHANGED: &str = "user.password_changed";
+pub const USER_PASSWORD_RESET: &str = "user.password_reset";
+pub const USER_DELETED: &str = "user.deleted";
+pub const USER_RESTORED: &str = "user.restored";
+pub const USER_PURGED: &str = "user.purged";
+pub const USER_TWO_FACTOR_ENABLED: &str = "user.two_factor_enabled";
+pub const USER_TWO_FACTOR_DISABLED: &str = "user.two_factor_disabled";
+pub const USER_UNLOCKED: &str = "user.unlocked";
+pub const USER_ROLES_UPDATED: &str = "user.roles_updated";
+pub const ROLE_CREATED: &str = "role.created";
+pub const ROLE_UPDATED: &str = "role.updated";
+pub const ROLE_DELETED: &str = "role.deleted";
+
+pub struct AuditEntry<'a> {
+    pub actor_id: Option<i64>,
+    pub action: &'a str,
+    // id user, atau id role untuk aksi role.*
+    pub target_id: i64,
+    pub changes: Value,
+    pub client: &'a ClientInfo,
+}
+
+// simpan audit log, dipanggil di dalam transaksi yang sama dengan perubahan datanya
+pub async fn record<'e, E>(executor: E, entry: AuditEntry<'_>) -> Result<(), sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    sqlx::query!(
+        r#"
+        INSERT INTO audit_logs (actor_id, action, target_id, changes, ip_address, user_agent)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+        entry.actor_id,
+        entry.action,
+        entry.target_id,
+        entry.changes,
+        entry.client.ip,
+        entry.client.user_agent
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+// field rahasia yang isinya tidak pernah disimpan di audit log, hanya tanda bahwa field berubah
+const REDACTED_FIELDS: &[&str] = &["password", "two_factor_secret"];
+
+const REDACTED: &str = "[redacted]";
+
+// {"before": {...}, "after": {...}} yang hanya berisi field yang berubah
+pub fn diff(before: &Value, after: &Value) -> Value {
+    let empty = Map::new();
+    let before = before.as_object().unwrap_or(&empty);
+    let after = after.as_object().unwrap_or(&empty);
+
+    let mut changed_before = Map::new();
+    let mut changed_after = Map::new();
+
+    for key in before.keys().chain(after.keys()) {
+        let old = before.get(key).cloned().unwrap_or(Value::Null);
+        let new = after.get(key).cloned().unwrap_or(Value::Null);
+
+        if old == new {
+            continue;
+        }
+
+        if REDACTED_FIELDS.contains(&key.as_str()) {
+            changed_before.insert(key.clone(), json!(REDACTED));
+            changed_after.insert(key.clone(), json!(REDACTED));
+        } else {
+            changed_before.insert(key.clone(), old);
+            changed_after.insert(key.clone(), new);
+        }
+    }
+
+    json!({ "before": changed_before, "after": changed_after })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_keeps_only_changed_fields() {
+        let changes = diff(
+            &json!({ "name": "Budi", "email": "budi@example.com", "locale": null }),
+            &json!({ "name": "Budi Santoso", "email": "budi@example.com", "locale": "en" }),
+        );
+
+        assert_eq!(
+            changes,
+            json!({
+                "before": { "name": "Budi", "locale": null },
+                "after": { "name": "Budi Santoso", "locale": "en" },
+            })
+        );
+    }
+
+    #[test]
+    fn diff_of_identical_values_is_empty() {
+        let value = json!({ "roles": ["admin", "member"] });
+
+        assert_eq!(diff(&value, &value), json!({ "before": {}, "after": {} }));
+    }
+
+    #[test]
+    fn diff_treats_missing_fields_as_null() {
+        let changes = diff(&json!({ "name": "Budi" }), &json!({ "locale": "id" }));
+
+        assert_eq!(
+            changes,
+            json!({
+                "before": { "name": "Budi", "locale": null },
+                "after": { "name": null, "locale": "id" },
+            })
+        );
+    }
+
+    #[test]
+    fn diff_ignores_non_object_values() {
+        assert_eq!(
+            diff(&Value::Null, &json!("text")),
+            json!({ "before": {}, "after": {} })
+        );
+    }
+
+    #[test]
+    fn diff_never_records_secrets() {
+        let changes = diff(
+            &json!({ "name": "Budi", "password": "$2b$10$old", "two_factor_secret": null }),
+            &json!({ "name": "Budi", "password": "$2b$10$new", "two_factor_secret": "JBSWY3DP" }),
+        );
+
+        assert_eq!(
+            changes,
+            json!({
+                "before": { "password": REDACTED, "two_factor_secret": REDACTED },
+                "after": { "password": REDACTED, "two_factor_secret": REDACTED },
+            })
+        );
+
+        let serialized = changes.to_string();
+        assert!(!serialized.contains("$2b$"));
+        assert!(!serialized.contains("JBSWY3DP"));
+    }
+}
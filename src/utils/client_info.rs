@@ -0,0 +1,63 @@
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header::USER_AGENT, request::Parts},
+};
+use std::{
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use crate::config::app::AppConfig;
+
+// IP dan user agent pemanggil, dipakai untuk audit log
+#[derive(Clone, Debug, Default)]
+pub struct ClientInfo {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl<S> FromRequestParts<S> for ClientInfo
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        // header X-Forwarded-For hanya dipercaya bila aplikasi berada di belakang proxy
+        let trust_proxy = parts
+            .extensions
+            .get::<Arc<AppConfig>>()
+            .is_some_and(|config| config.app.trust_proxy);
+
+        // entry paling kanan ditambahkan oleh proxy kita, entry di kirinya bisa diisi bebas oleh client
+        // isi header yang bukan IP valid diabaikan, kolom ip_address hanya VARCHAR(45)
+        let forwarded = trust_proxy
+            .then(|| {
+                parts
+                    .headers
+                    .get("x-forwarded-for")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.rsplit(',').next())
+                    .and_then(|v| v.trim().parse::<IpAddr>().ok())
+            })
+            .flatten();
+
+        let ip = forwarded
+            .or_else(|| {
+                parts
+                    .extensions
+                    .get::<ConnectInfo<SocketAddr>>()
+                    .map(|ConnectInfo(addr)| addr.ip())
+            })
+            .map(|ip| ip.to_string());
+
+        let user_agent = parts
+            .headers
+            .get(USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.chars().take(255).collect());
+
+        Ok(Self { ip, user_agent })
+    }
+}
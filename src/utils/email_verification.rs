@@ -0,0 +1,52 @@
+use chrono::{Duration, Utc};
+use sqlx::{Executor, MySql};
+
+use crate::utils::signed_token;
+
+// purpose yang ikut ditandatangani di token verifikasi
+pub const EMAIL_VERIFICATION_PURPOSE: &str = "email-verification";
+
+// masa berlaku link verifikasi email
+pub const EMAIL_VERIFICATION_TTL_HOURS: i64 = 24;
+
+// hapus link verifikasi yang belum dipakai, token hanya terikat ke user_id
+// sehingga link lama untuk email sebelumnya tidak boleh memverifikasi email baru
+pub async fn revoke_verification_tokens<'e, E>(executor: E, user_id: i64) -> Result<(), sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    sqlx::query!(
+        "DELETE FROM email_verification_tokens WHERE user_id = ? AND used_at IS NULL",
+        user_id
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+// simpan hash token verifikasi baru, kembalikan token plaintext untuk dikirim via email
+pub async fn issue_verification_token<'e, E>(
+    executor: E,
+    user_id: i64,
+) -> Result<String, sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    let (token, token_hash) = signed_token::generate(EMAIL_VERIFICATION_PURPOSE);
+    let expires_at = Utc::now() + Duration::hours(EMAIL_VERIFICATION_TTL_HOURS);
+
+    sqlx::query!(
+        r#"
+        INSERT INTO email_verification_tokens (user_id, token_hash, expires_at)
+        VALUES (?, ?, ?)
+        "#,
+        user_id,
+        token_hash,
+        expires_at
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(token)
+}
@@ -1,44 +1,175 @@
 use jsonwebtoken::{
-    encode, decode, Header, EncodingKey, DecodingKey,
-    Validation, errors::Error as JwtError
+    encode, decode, decode_header, Header, EncodingKey, DecodingKey,
+    Validation, errors::{Error as JwtError, ErrorKind}
 };
-use serde::{Serialize, Deserialize};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
 use chrono::{Utc, Duration};
+use rand::{RngCore, rngs::OsRng};
+use std::sync::OnceLock;
+
+use crate::utils::{jwt_keys::keys, signed_token};
+
+// data user yang ikut di access token, supaya handler tidak perlu query database
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+
+pub struct UserClaims{
+    pub name: String,
+    pub email: String,
+    pub roles: Vec<String>,
+    // sesi dimulai lewat login 2FA, permission admin hanya berlaku bila true
+    #[serde(default)]
+    pub mfa: bool,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 
-pub struct Claims{
+pub struct Claims<T = UserClaims>{
     pub sub: i64, 
     pub exp: usize,
+    pub iat: usize,
+    pub nbf: usize,
+    pub iss: String,
+    pub aud: Vec<String>,
+    pub jti: String,
+    // claim tambahan, di-serialize sejajar dengan claim standar
+    #[serde(flatten)]
+    pub extra: T,
+}
+
+// issuer, audience dan toleransi selisih jam, di-load sekali saat startup
+pub struct JwtOptions{
+    pub issuer: String,
+    pub audience: Vec<String>,
+    pub leeway_seconds: u64,
+    // masa berlaku access token, diperbarui lewat refresh token
+    pub access_token_ttl_minutes: i64,
+}
+
+static OPTIONS: OnceLock<JwtOptions> = OnceLock::new();
+
+// dipanggil sekali saat startup dari main
+pub fn init(options: JwtOptions) {
+    if OPTIONS.set(options).is_err() {
+        panic!("JWT options sudah di-load");
+    }
+}
+
+fn options() -> &'static JwtOptions {
+    OPTIONS.get().expect("JWT options belum di-load")
 }
 
-pub fn generate_token(user_id: i64) -> Result<String, JwtError> {
-    let exp = Utc::now()
-        .checked_add_signed(Duration::hours(24))
+// id unik token, dipakai untuk mencabut token saat logout
+fn generate_jti() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+pub fn generate_token<T: Serialize>(user_id: i64, extra: T) -> Result<String, JwtError> {
+    let keys = keys();
+    let options = options();
+
+    let now = Utc::now();
+    let exp = now
+        .checked_add_signed(Duration::minutes(options.access_token_ttl_minutes))
         .unwrap()
         .timestamp() as usize;
 
+    let mut header = Header::new(keys.algorithm);
+    header.kid = keys.kid.clone();
+
     encode(
-        &Header::default(),
-        &Claims{ sub: user_id, exp},
-        &EncodingKey::from_secret(
-            std::env::var("JWT_SECRET")
-                .unwrap_or_else(|_| "secret".to_string())
-                .as_ref()
-        )
+        &header,
+        &Claims{
+            sub: user_id,
+            exp,
+            iat: now.timestamp() as usize,
+            nbf: now.timestamp() as usize,
+            iss: options.issuer.clone(),
+            aud: options.audience.clone(),
+            jti: generate_jti(),
+            extra,
+        },
+        &keys.encoding
     )
 }
 
-pub fn verify_token(token: &str) -> Result <Claims, JwtError>{
-    let token_data = decode::<Claims>(
+pub fn verify_token<T: DeserializeOwned>(token: &str) -> Result <Claims<T>, JwtError>{
+    // pilih key berdasarkan kid supaya token dari key lama tetap valid selama rotasi
+    let header = decode_header(token)?;
+    let key = keys()
+        .find(header.kid.as_deref())
+        .ok_or_else(|| JwtError::from(ErrorKind::InvalidSignature))?;
+
+    // hanya terima token yang diterbitkan untuk API ini
+    let options = options();
+    let mut validation = Validation::new(key.algorithm);
+    validation.leeway = options.leeway_seconds;
+    validation.validate_nbf = true;
+    validation.set_issuer(&[&options.issuer]);
+    validation.set_audience(&options.audience);
+    validation.set_required_spec_claims(&["exp", "nbf", "iss", "aud", "sub"]);
+
+    let token_data = decode::<Claims<T>>(
         token, 
-        &DecodingKey::from_secret(
-            std::env::var("JWT_SECRET")
-                .unwrap_or_else(|_| "secret".to_string())
-                .as_ref()
-        ),
+        &key.decoding,
+        &validation
+    )?;
+
+    // iat di masa depan berarti token dibuat dengan jam yang salah
+    let now = Utc::now().timestamp() as u64;
+    if token_data.claims.iat as u64 > now + options.leeway_seconds {
+        return Err(ErrorKind::ImmatureSignature.into());
+    }
+
+    Ok(token_data.claims)
+}
+
+// token sementara setelah password benar, ditukar dengan JWT di /api/login/2fa
+#[derive(Serialize, Deserialize, Clone, Debug)]
+
+pub struct MfaClaims{
+    pub sub: i64,
+    pub exp: usize,
+    pub iat: usize,
+    // token sekali pakai, jti dicatat di revoked_tokens setelah dipakai
+    pub jti: String,
+}
+
+// masa berlaku token "mfa pending"
+pub const MFA_TOKEN_TTL_MINUTES: i64 = 5;
+
+// key berbeda dari access token supaya token MFA tidak bisa dipakai sebagai access token
+// dan tidak bisa diverifikasi service lain lewat JWKS
+fn mfa_secret() -> Vec<u8> {
+    [b"mfa:".as_slice(), signed_token::secret()].concat()
+}
+
+pub fn generate_mfa_token(user_id: i64) -> Result<String, JwtError> {
+    let now = Utc::now();
+    let exp = now
+        .checked_add_signed(Duration::minutes(MFA_TOKEN_TTL_MINUTES))
+        .unwrap()
+        .timestamp() as usize;
+
+    encode(
+        &Header::default(),
+        &MfaClaims{
+            sub: user_id,
+            exp,
+            iat: now.timestamp() as usize,
+            jti: generate_jti(),
+        },
+        &EncodingKey::from_secret(&mfa_secret())
+    )
+}
+
+pub fn verify_mfa_token(token: &str) -> Result<MfaClaims, JwtError>{
+    let token_data = decode::<MfaClaims>(
+        token,
+        &DecodingKey::from_secret(&mfa_secret()),
         &Validation::default()
     )?;
 
     Ok(token_data.claims)
-}
\ No newline at end of file
+}
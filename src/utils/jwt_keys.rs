@@ -0,0 +1,199 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey,
+    jwk::{
+        AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+        OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+    },
+};
+use rsa::{
+    RsaPublicKey, pkcs1::DecodeRsaPublicKey, pkcs8::DecodePublicKey, traits::PublicKeyParts,
+};
+use std::{fmt, sync::OnceLock};
+
+// prefix DER SubjectPublicKeyInfo untuk public key Ed25519 (RFC 8410), diikuti 32 byte key
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+#[derive(Debug)]
+pub struct KeyError(pub String);
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// key yang dipakai untuk memverifikasi token, jwk hanya ada untuk public key
+pub struct VerificationKey {
+    pub kid: Option<String>,
+    pub algorithm: Algorithm,
+    pub decoding: DecodingKey,
+    pub jwk: Option<Jwk>,
+}
+
+impl VerificationKey {
+    // public key RS256 / EdDSA dalam format PEM
+    pub fn from_public_pem(kid: &str, algorithm: Algorithm, pem: &[u8]) -> Result<Self, KeyError> {
+        let parameters = match algorithm {
+            Algorithm::RS256 => rsa_parameters(pem)?,
+            Algorithm::EdDSA => ed25519_parameters(pem)?,
+            _ => {
+                return Err(KeyError(format!(
+                    "algoritma {:?} tidak didukung untuk public key",
+                    algorithm
+                )));
+            }
+        };
+
+        let jwk = Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(match algorithm {
+                    Algorithm::EdDSA => KeyAlgorithm::EdDSA,
+                    _ => KeyAlgorithm::RS256,
+                }),
+                key_id: Some(kid.to_string()),
+                ..Default::default()
+            },
+            algorithm: parameters,
+        };
+
+        let decoding = DecodingKey::from_jwk(&jwk)
+            .map_err(|e| KeyError(format!("public key \"{}\" tidak valid: {}", kid, e)))?;
+
+        Ok(Self {
+            kid: Some(kid.to_string()),
+            algorithm,
+            decoding,
+            jwk: Some(jwk),
+        })
+    }
+}
+
+fn rsa_parameters(pem: &[u8]) -> Result<AlgorithmParameters, KeyError> {
+    let pem = std::str::from_utf8(pem).map_err(|_| KeyError("PEM bukan UTF-8".to_string()))?;
+
+    // terima format "PUBLIC KEY" (SPKI) maupun "RSA PUBLIC KEY" (PKCS#1)
+    let key = RsaPublicKey::from_public_key_pem(pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(pem))
+        .map_err(|e| KeyError(format!("RSA public key tidak valid: {}", e)))?;
+
+    Ok(AlgorithmParameters::RSA(RSAKeyParameters {
+        key_type: RSAKeyType::RSA,
+        n: URL_SAFE_NO_PAD.encode(key.n().to_bytes_be()),
+        e: URL_SAFE_NO_PAD.encode(key.e().to_bytes_be()),
+    }))
+}
+
+fn ed25519_parameters(pem: &[u8]) -> Result<AlgorithmParameters, KeyError> {
+    let pem = pem::parse(pem).map_err(|e| KeyError(format!("PEM tidak valid: {}", e)))?;
+    let der = pem.contents();
+
+    let raw = der
+        .strip_prefix(&ED25519_SPKI_PREFIX[..])
+        .filter(|raw| pem.tag() == "PUBLIC KEY" && raw.len() == 32)
+        .ok_or_else(|| KeyError("Ed25519 public key tidak valid".to_string()))?;
+
+    Ok(AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+        key_type: OctetKeyPairType::OctetKeyPair,
+        curve: EllipticCurve::Ed25519,
+        x: URL_SAFE_NO_PAD.encode(raw),
+    }))
+}
+
+// key aktif untuk menandatangani token baru + semua key yang masih diterima saat verifikasi
+pub struct JwtKeys {
+    pub algorithm: Algorithm,
+    pub kid: Option<String>,
+    pub encoding: EncodingKey,
+    pub verification: Vec<VerificationKey>,
+}
+
+impl JwtKeys {
+    // HS256 dengan shared secret, tidak pernah dipublikasikan lewat JWKS
+    pub fn hmac(kid: Option<String>, secret: &[u8]) -> Self {
+        Self {
+            algorithm: Algorithm::HS256,
+            verification: vec![VerificationKey {
+                kid: kid.clone(),
+                algorithm: Algorithm::HS256,
+                decoding: DecodingKey::from_secret(secret),
+                jwk: None,
+            }],
+            kid,
+            encoding: EncodingKey::from_secret(secret),
+        }
+    }
+
+    // private key PEM untuk key aktif, public key-nya harus ada di `verification` dengan kid yang sama
+    pub fn asymmetric(
+        algorithm: Algorithm,
+        kid: String,
+        private_pem: &[u8],
+        verification: Vec<VerificationKey>,
+    ) -> Result<Self, KeyError> {
+        let encoding = match algorithm {
+            Algorithm::RS256 => EncodingKey::from_rsa_pem(private_pem),
+            Algorithm::EdDSA => EncodingKey::from_ed_pem(private_pem),
+            _ => {
+                return Err(KeyError(format!(
+                    "algoritma {:?} tidak didukung untuk private key",
+                    algorithm
+                )));
+            }
+        }
+        .map_err(|e| KeyError(format!("private key \"{}\" tidak valid: {}", kid, e)))?;
+
+        if !verification
+            .iter()
+            .any(|key| key.kid.as_deref() == Some(kid.as_str()))
+        {
+            return Err(KeyError(format!(
+                "public key untuk kid \"{}\" tidak ditemukan",
+                kid
+            )));
+        }
+
+        Ok(Self {
+            algorithm,
+            kid: Some(kid),
+            encoding,
+            verification,
+        })
+    }
+
+    // cari key berdasarkan header kid, token lama tanpa kid memakai key aktif
+    pub fn find(&self, kid: Option<&str>) -> Option<&VerificationKey> {
+        let kid = kid.or(self.kid.as_deref());
+
+        self.verification
+            .iter()
+            .find(|key| key.kid.as_deref() == kid)
+    }
+
+    // public key untuk GET /.well-known/jwks.json
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self
+                .verification
+                .iter()
+                .filter_map(|key| key.jwk.clone())
+                .collect(),
+        }
+    }
+}
+
+static KEYS: OnceLock<JwtKeys> = OnceLock::new();
+
+// dipanggil sekali saat startup dari main
+pub fn init(keys: JwtKeys) {
+    if KEYS.set(keys).is_err() {
+        panic!("JWT keys sudah di-load");
+    }
+}
+
+pub fn keys() -> &'static JwtKeys {
+    KEYS.get().expect("JWT keys belum di-load")
+}
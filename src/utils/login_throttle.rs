@@ -0,0 +1,462 @@
+use chrono::{DateTime, Duration, Utc};
+use sqlx::MySqlPool;
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Mutex};
+
+// hitungan gagal direset bila percobaan terakhir lebih lama dari window ini
+const FAILURE_WINDOW_MINUTES: i64 = 60;
+
+// lama akun/IP dikunci setelah melewati batas gagal
+const LOCKOUT_MINUTES: i64 = 15;
+
+// jeda maksimal dari exponential backoff sebelum lockout
+const MAX_BACKOFF_SECONDS: i64 = 300;
+
+// kode 2FA yang boleh salah per token "mfa pending" sebelum token dibakar
+pub const MFA_TOKEN_MAX_FAILURES: u32 = 5;
+
+// jumlah entry sebelum entry yang sudah basi dibersihkan dari memory store
+const PRUNE_THRESHOLD: usize = 10_000;
+
+struct Policy {
+    // percobaan gagal yang dibiarkan tanpa jeda
+    free_attempts: u32,
+    // percobaan gagal sebelum dikunci selama LOCKOUT_MINUTES
+    lockout_after: u32,
+}
+
+const ACCOUNT_POLICY: Policy = Policy {
+    free_attempts: 3,
+    lockout_after: 10,
+};
+
+// satu IP bisa dipakai banyak user (NAT, kantor), jadi batasnya lebih longgar
+const IP_POLICY: Policy = Policy {
+    free_attempts: 10,
+    lockout_after: 50,
+};
+
+impl Policy {
+    // lama kunci setelah gagal ke-n: 1, 2, 4, 8 ... detik lalu lockout penuh
+    fn delay(&self, failures: u32) -> Option<Duration> {
+        if failures >= self.lockout_after {
+            return Some(Duration::minutes(LOCKOUT_MINUTES));
+        }
+
+        // gagal ke-(free_attempts + 1) mendapat jeda pertama 1 detik
+        let exponent = failures.checked_sub(self.free_attempts + 1)?;
+        let seconds = 1i64
+            .checked_shl(exponent)
+            .unwrap_or(MAX_BACKOFF_SECONDS)
+            .min(MAX_BACKOFF_SECONDS);
+
+        Some(Duration::seconds(seconds))
+    }
+}
+
+fn account_key(email: &str) -> String {
+    format!("account:{}", email.trim().to_lowercase())
+}
+
+fn ip_key(ip: &str) -> String {
+    format!("ip:{}", ip)
+}
+
+fn mfa_token_key(jti: &str) -> String {
+    format!("mfa:{}", jti)
+}
+
+pub type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, sqlx::Error>> + Send + 'a>>;
+
+// penyimpanan hitungan gagal login, memory untuk satu node atau database untuk banyak node
+pub trait AttemptStore: Send + Sync {
+    // waktu kunci yang tersimpan untuk key ini (bisa sudah lewat)
+    fn locked_until<'a>(&'a self, key: &'a str) -> StoreFuture<'a, Option<DateTime<Utc>>>;
+
+    // tambah hitungan gagal dan kembalikan jumlahnya dalam window saat ini
+    fn add_failure<'a>(&'a self, key: &'a str) -> StoreFuture<'a, u32>;
+
+    fn lock<'a>(&'a self, key: &'a str, until: DateTime<Utc>) -> StoreFuture<'a, ()>;
+
+    fn clear<'a>(&'a self, key: &'a str) -> StoreFuture<'a, ()>;
+}
+
+struct MemoryEntry {
+    failures: u32,
+    last_failed_at: DateTime<Utc>,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+pub struct MemoryAttemptStore {
+    entries: Mutex<HashMap<String, MemoryEntry>>,
+}
+
+impl MemoryAttemptStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl AttemptStore for MemoryAttemptStore {
+    fn locked_until<'a>(&'a self, key: &'a str) -> StoreFuture<'a, Option<DateTime<Utc>>> {
+        let locked_until = self
+            .entries
+            .lock()
+            .unwrap()
+            .get(key)
+            .and_then(|entry| entry.locked_until);
+
+        Box::pin(async move { Ok(locked_until) })
+    }
+
+    fn add_failure<'a>(&'a self, key: &'a str) -> StoreFuture<'a, u32> {
+        let now = Utc::now();
+        let window_start = now - Duration::minutes(FAILURE_WINDOW_MINUTES);
+        let mut entries = self.entries.lock().unwrap();
+
+        if entries.len() >= PRUNE_THRESHOLD {
+            entries.retain(|_, entry| {
+                entry.last_failed_at > window_start
+                    || entry.locked_until.is_some_and(|until| until > now)
+            });
+        }
+
+        let entry = entries.entry(key.to_string()).or_insert(MemoryEntry {
+            failures: 0,
+            last_failed_at: now,
+            locked_until: None,
+        });
+
+        if entry.last_failed_at <= window_start {
+            entry.failures = 0;
+        }
+
+        entry.failures += 1;
+        entry.last_failed_at = now;
+
+        let failures = entry.failures;
+
+        Box::pin(async move { Ok(failures) })
+    }
+
+    fn lock<'a>(&'a self, key: &'a str, until: DateTime<Utc>) -> StoreFuture<'a, ()> {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(key) {
+            entry.locked_until = Some(until);
+        }
+
+        Box::pin(async { Ok(()) })
+    }
+
+    fn clear<'a>(&'a self, key: &'a str) -> StoreFuture<'a, ()> {
+        self.entries.lock().unwrap().remove(key);
+
+        Box::pin(async { Ok(()) })
+    }
+}
+
+// disimpan di tabel login_attempts supaya berlaku di semua node
+pub struct DatabaseAttemptStore {
+    db: MySqlPool,
+}
+
+impl DatabaseAttemptStore {
+    pub fn new(db: MySqlPool) -> Self {
+        Self { db }
+    }
+}
+
+impl AttemptStore for DatabaseAttemptStore {
+    fn locked_until<'a>(&'a self, key: &'a str) -> StoreFuture<'a, Option<DateTime<Utc>>> {
+        Box::pin(async move {
+            let row = sqlx::query!(
+                "SELECT locked_until FROM login_attempts WHERE attempt_key = ?",
+                key
+            )
+            .fetch_optional(&self.db)
+            .await?;
+
+            Ok(row.and_then(|row| row.locked_until))
+        })
+    }
+
+    fn add_failure<'a>(&'a self, key: &'a str) -> StoreFuture<'a, u32> {
+        Box::pin(async move {
+            // failures dihitung dari last_failed_at lama karena di-assign lebih dulu
+            sqlx::query!(
+                r#"
+                INSERT INTO login_attempts (attempt_key, failures, last_failed_at)
+                VALUES (?, 1, NOW())
+                ON DUPLICATE KEY UPDATE
+                    failures = IF(last_failed_at <= NOW() - INTERVAL ? MINUTE, 1, failures + 1),
+                    last_failed_at = NOW()
+                "#,
+                key,
+                FAILURE_WINDOW_MINUTES
+            )
+            .execute(&self.db)
+            .await?;
+
+            let row = sqlx::query!(
+                "SELECT failures FROM login_attempts WHERE attempt_key = ?",
+                key
+            )
+            .fetch_one(&self.db)
+            .await?;
+
+            Ok(row.failures)
+        })
+    }
+
+    fn lock<'a>(&'a self, key: &'a str, until: DateTime<Utc>) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            sqlx::query!(
+                "UPDATE login_attempts SET locked_until = ? WHERE attempt_key = ?",
+                until,
+                key
+            )
+            .execute(&self.db)
+            .await?;
+
+            // bersihkan key yang sudah tidak gagal dan tidak terkunci lagi
+            sqlx::query!(
+                r#"
+                DELETE FROM login_attempts
+                WHERE last_failed_at <= NOW() - INTERVAL ? MINUTE
+                AND (locked_until IS NULL OR locked_until <= NOW())
+                "#,
+                FAILURE_WINDOW_MINUTES
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn clear<'a>(&'a self, key: &'a str) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            sqlx::query!("DELETE FROM login_attempts WHERE attempt_key = ?", key)
+                .execute(&self.db)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
+
+// pembatas percobaan login per akun (email) dan per IP
+pub struct LoginThrottle {
+    store: Box<dyn AttemptStore>,
+}
+
+impl LoginThrottle {
+    pub fn new(store: Box<dyn AttemptStore>) -> Self {
+        Self { store }
+    }
+
+    fn keys(email: &str, ip: Option<&str>) -> Vec<(String, &'static Policy)> {
+        let mut keys = vec![(account_key(email), &ACCOUNT_POLICY)];
+
+        if let Some(ip) = ip {
+            keys.push((ip_key(ip), &IP_POLICY));
+        }
+
+        keys
+    }
+
+    // sisa detik sampai boleh mencoba lagi, None bila tidak sedang dikunci
+    pub async fn retry_after(
+        &self,
+        email: &str,
+        ip: Option<&str>,
+    ) -> Result<Option<u64>, sqlx::Error> {
+        let now = Utc::now();
+        let mut retry_after = None;
+
+        for (key, _) in Self::keys(email, ip) {
+            if let Some(until) = self.store.locked_until(&key).await?
+                && until > now
+            {
+                let seconds = (until - now).num_seconds().max(1) as u64;
+                retry_after = retry_after.max(Some(seconds));
+            }
+        }
+
+        Ok(retry_after)
+    }
+
+    // catat login gagal, kembalikan sisa detik bila akun/IP sekarang dikunci
+    pub async fn record_failure(
+        &self,
+        email: &str,
+        ip: Option<&str>,
+    ) -> Result<Option<u64>, sqlx::Error> {
+        let mut retry_after = None;
+
+        for (key, policy) in Self::keys(email, ip) {
+            let failures = self.store.add_failure(&key).await?;
+
+            if let Some(delay) = policy.delay(failures) {
+                self.store.lock(&key, Utc::now() + delay).await?;
+                retry_after = retry_after.max(Some(delay.num_seconds() as u64));
+            }
+        }
+
+        Ok(retry_after)
+    }
+
+    // catat kode 2FA salah untuk satu token "mfa pending", true bila token harus dibakar
+    pub async fn record_mfa_failure(&self, jti: &str) -> Result<bool, sqlx::Error> {
+        let failures = self.store.add_failure(&mfa_token_key(jti)).await?;
+
+        Ok(failures >= MFA_TOKEN_MAX_FAILURES)
+    }
+
+    // login berhasil: hitungan akun direset, hitungan IP tetap supaya tidak bisa di-reset lewat akun lain
+    pub async fn record_success(&self, email: &str) -> Result<(), sqlx::Error> {
+        self.store.clear(&account_key(email)).await
+    }
+
+    // dipakai admin untuk membuka kunci akun
+    pub async fn unlock(&self, email: &str) -> Result<(), sqlx::Error> {
+        self.store.clear(&account_key(email)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMAIL: &str = "user@example.com";
+
+    fn throttle() -> LoginThrottle {
+        LoginThrottle::new(Box::new(MemoryAttemptStore::new()))
+    }
+
+    fn seconds(delay: Option<Duration>) -> Option<i64> {
+        delay.map(|delay| delay.num_seconds())
+    }
+
+    #[test]
+    fn account_delay_doubles_after_free_attempts() {
+        let delays: Vec<Option<i64>> = (1..=10)
+            .map(|failures| seconds(ACCOUNT_POLICY.delay(failures)))
+            .collect();
+
+        assert_eq!(
+            delays,
+            [
+                None,
+                None,
+                None,
+                Some(1),
+                Some(2),
+                Some(4),
+                Some(8),
+                Some(16),
+                Some(32),
+                Some(LOCKOUT_MINUTES * 60),
+            ]
+        );
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        assert_eq!(seconds(IP_POLICY.delay(19)), Some(256));
+        assert_eq!(seconds(IP_POLICY.delay(20)), Some(MAX_BACKOFF_SECONDS));
+        assert_eq!(seconds(IP_POLICY.delay(49)), Some(MAX_BACKOFF_SECONDS));
+
+        // exponent di luar jangkauan shift tidak overflow
+        let policy = Policy {
+            free_attempts: 0,
+            lockout_after: u32::MAX,
+        };
+        assert_eq!(seconds(policy.delay(100)), Some(MAX_BACKOFF_SECONDS));
+    }
+
+    #[test]
+    fn lockout_applies_from_threshold() {
+        let lockout = Some(LOCKOUT_MINUTES * 60);
+
+        assert_eq!(seconds(ACCOUNT_POLICY.delay(10)), lockout);
+        assert_eq!(seconds(ACCOUNT_POLICY.delay(11)), lockout);
+        assert_eq!(seconds(IP_POLICY.delay(50)), lockout);
+    }
+
+    #[tokio::test]
+    async fn failure_reports_lock_once_threshold_is_crossed() {
+        let throttle = throttle();
+
+        for _ in 0..3 {
+            assert_eq!(throttle.record_failure(EMAIL, None).await.unwrap(), None);
+        }
+        assert_eq!(throttle.retry_after(EMAIL, None).await.unwrap(), None);
+
+        // gagal ke-4 langsung mengembalikan Retry-After
+        assert_eq!(throttle.record_failure(EMAIL, None).await.unwrap(), Some(1));
+        assert!(throttle.retry_after(EMAIL, None).await.unwrap().is_some());
+
+        for _ in 5..10 {
+            throttle.record_failure(EMAIL, None).await.unwrap();
+        }
+
+        let lockout = (LOCKOUT_MINUTES * 60) as u64;
+        assert_eq!(
+            throttle.record_failure(EMAIL, None).await.unwrap(),
+            Some(lockout)
+        );
+
+        let retry_after = throttle.retry_after(EMAIL, None).await.unwrap().unwrap();
+        assert!(retry_after > lockout - 5 && retry_after <= lockout);
+    }
+
+    #[tokio::test]
+    async fn account_key_ignores_case_and_whitespace() {
+        let throttle = throttle();
+
+        for _ in 0..3 {
+            throttle.record_failure(EMAIL, None).await.unwrap();
+        }
+
+        let locked = throttle
+            .record_failure(" User@Example.com ", None)
+            .await
+            .unwrap();
+        assert_eq!(locked, Some(1));
+    }
+
+    #[tokio::test]
+    async fn success_resets_account_but_not_ip() {
+        let throttle = throttle();
+        let ip = Some("203.0.113.7");
+
+        for _ in 0..5 {
+            throttle.record_failure(EMAIL, ip).await.unwrap();
+        }
+
+        throttle.record_success(EMAIL).await.unwrap();
+        assert_eq!(throttle.retry_after(EMAIL, None).await.unwrap(), None);
+
+        // hitungan akun mulai dari awal, hitungan IP tetap berjalan
+        assert_eq!(throttle.record_failure(EMAIL, None).await.unwrap(), None);
+
+        // IP sudah gagal 10 kali lewat akun lain, gagal ke-11 mendapat jeda
+        for _ in 0..5 {
+            let _ = throttle.record_failure("other@example.com", ip).await;
+        }
+
+        let locked = throttle.record_failure("third@example.com", ip).await;
+        assert_eq!(locked.unwrap(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn mfa_token_is_burned_after_max_failures() {
+        let throttle = throttle();
+
+        for _ in 1..MFA_TOKEN_MAX_FAILURES {
+            assert!(!throttle.record_mfa_failure("jti").await.unwrap());
+        }
+        assert!(throttle.record_mfa_failure("jti").await.unwrap());
+        assert!(!throttle.record_mfa_failure("other").await.unwrap());
+    }
+}
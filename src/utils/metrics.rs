@@ -0,0 +1,77 @@
+use metrics::{counter, gauge, histogram};
+use sqlx::MySqlPool;
+use std::time::{Duration, Instant};
+
+// nama metric Prometheus, dipakai juga oleh config::metrics untuk HELP & bucket
+pub const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+pub const LOGIN_ATTEMPTS_TOTAL: &str = "auth_login_attempts_total";
+pub const BCRYPT_DURATION_SECONDS: &str = "bcrypt_duration_seconds";
+pub const DB_POOL_SIZE: &str = "db_pool_size";
+pub const DB_POOL_IDLE: &str = "db_pool_idle";
+pub const DB_POOL_PROBE_DURATION_SECONDS: &str = "db_pool_probe_duration_seconds";
+
+// batas waktu probe acquire supaya /metrics tidak ikut menunggu acquire timeout pool (30 detik)
+const POOL_PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+// label route untuk request yang tidak cocok dengan route manapun (404)
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+// cara login: password di /api/login atau kode 2FA di /api/login/2fa
+pub const LOGIN_PASSWORD: &str = "password";
+pub const LOGIN_TWO_FACTOR: &str = "2fa";
+
+// hasil percobaan login
+pub const LOGIN_SUCCESS: &str = "success";
+pub const LOGIN_FAILURE: &str = "failure";
+pub const LOGIN_THROTTLED: &str = "throttled";
+pub const LOGIN_MFA_REQUIRED: &str = "mfa_required";
+pub const LOGIN_UNVERIFIED: &str = "unverified";
+
+// route memakai template (mis. /api/users/{id}) supaya jumlah label tidak meledak
+pub fn record_request(method: &str, route: &str, status: u16, started: Instant) {
+    let method = method.to_string();
+    let route = route.to_string();
+
+    counter!(
+        HTTP_REQUESTS_TOTAL,
+        "method" => method.clone(),
+        "route" => route.clone(),
+        "status" => status.to_string()
+    )
+    .increment(1);
+    histogram!(HTTP_REQUEST_DURATION_SECONDS, "method" => method, "route" => route)
+        .record(started.elapsed().as_secs_f64());
+}
+
+pub fn record_login(method: &'static str, result: &'static str) {
+    counter!(LOGIN_ATTEMPTS_TOTAL, "method" => method, "result" => result).increment(1);
+}
+
+pub fn record_bcrypt(operation: &'static str, started: Instant) {
+    histogram!(BCRYPT_DURATION_SECONDS, "operation" => operation)
+        .record(started.elapsed().as_secs_f64());
+}
+
+// dibaca saat /metrics di-scrape, waktu acquire diukur lewat probe: satu koneksi diambil dari pool
+// dengan batas POOL_PROBE_TIMEOUT, probe yang timeout dicatat sebesar batas tersebut
+pub async fn record_pool_stats(db: &MySqlPool) {
+    gauge!(DB_POOL_SIZE).set(db.size() as f64);
+    gauge!(DB_POOL_IDLE).set(db.num_idle() as f64);
+
+    let started = Instant::now();
+
+    match tokio::time::timeout(POOL_PROBE_TIMEOUT, db.acquire()).await {
+        Ok(Ok(_)) => {
+            histogram!(DB_POOL_PROBE_DURATION_SECONDS).record(started.elapsed().as_secs_f64())
+        }
+        Ok(Err(e)) => tracing::warn!(error = %e, "Failed to acquire database connection"),
+        Err(_) => {
+            histogram!(DB_POOL_PROBE_DURATION_SECONDS).record(POOL_PROBE_TIMEOUT.as_secs_f64());
+            tracing::warn!(
+                timeout_seconds = POOL_PROBE_TIMEOUT.as_secs(),
+                "Database pool probe timed out"
+            );
+        }
+    }
+}
@@ -1,2 +1,23 @@
+pub mod app_error;
+pub mod audit;
+pub mod client_info;
+pub mod email_verification;
 pub mod jwt;
-pub mod response;
\ No newline at end of file
+pub mod jwt_keys;
+pub mod login_throttle;
+pub mod metrics;
+pub mod openapi;
+pub mod pagination;
+pub mod password;
+pub mod password_reset;
+pub mod permission;
+pub mod problem;
+pub mod rate_limit;
+pub mod refresh_token;
+pub mod response;
+pub mod revocation;
+pub mod search;
+pub mod shutdown;
+pub mod signed_token;
+pub mod totp;
+pub mod validated_json;
\ No newline at end of file
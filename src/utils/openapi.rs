@@ -0,0 +1,39 @@
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+};
+
+// dokumen OpenAPI 3, path & schema dikumpulkan dari route yang didaftarkan lewat OpenApiRouter
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Backend API", description = "REST API user management dengan autentikasi JWT"),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = "auth", description = "Register, login, token & verifikasi email"),
+        (name = "me", description = "Profil & 2FA user yang sedang login"),
+        (name = "users", description = "Manajemen user"),
+        (name = "roles", description = "Role & permission"),
+        (name = "audit", description = "Audit log perubahan user"),
+        (name = "health", description = "Liveness & readiness probe"),
+    )
+)]
+pub struct ApiDoc;
+
+// access token dikirim lewat header Authorization: Bearer <token>
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// jumlah data per halaman bila per_page tidak dikirim
+pub const DEFAULT_PER_PAGE: u32 = 15;
+
+#[derive(Serialize, ToSchema)]
+pub struct PaginationLinks {
+    pub first: String,
+    pub prev: Option<String>,
+    pub next: Option<String>,
+    pub last: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PaginationMeta {
+    pub total: i64,
+    pub per_page: u32,
+    // hanya ada pada mode page, tidak pada mode cursor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_page: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_page: Option<u32>,
+    pub next_cursor: Option<i64>,
+    pub links: PaginationLinks,
+}
+
+pub struct Sort {
+    pub column: &'static str,
+    pub descending: bool,
+}
+
+impl Sort {
+    pub fn direction(&self) -> &'static str {
+        if self.descending { "DESC" } else { "ASC" }
+    }
+}
+
+// parse "name" / "-created_at", hanya kolom yang ada di whitelist yang diterima
+pub fn parse_sort(value: &str, allowed: &[&'static str]) -> Option<Sort> {
+    let (field, descending) = match value.strip_prefix('-') {
+        Some(field) => (field, true),
+        None => (value, false),
+    };
+
+    allowed
+        .iter()
+        .find(|column| **column == field)
+        .map(|column| Sort { column, descending })
+}
+
+// escape wildcard LIKE dari input user
+pub fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+// pola LIKE "mengandung"
+pub fn like_contains(value: &str) -> String {
+    format!("%{}%", escape_like(value))
+}
+
+// halaman terakhir minimal 1, per_page 0 dianggap 1 supaya tidak membagi dengan nol
+pub fn last_page(total: i64, per_page: u32) -> u32 {
+    ((total.max(0) as u64).div_ceil(per_page.max(1) as u64) as u32).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIELDS: &[&str] = &["id", "name", "created_at"];
+
+    #[test]
+    fn parse_sort_accepts_whitelisted_fields() {
+        let sort = parse_sort("name", FIELDS).unwrap();
+        assert_eq!(sort.column, "name");
+        assert!(!sort.descending);
+        assert_eq!(sort.direction(), "ASC");
+
+        let sort = parse_sort("-created_at", FIELDS).unwrap();
+        assert_eq!(sort.column, "created_at");
+        assert!(sort.descending);
+        assert_eq!(sort.direction(), "DESC");
+    }
+
+    #[test]
+    fn parse_sort_rejects_unknown_fields() {
+        for value in [
+            "",
+            "-",
+            "--id",
+            "password",
+            "name desc",
+            "id;DROP TABLE users",
+            "Name",
+        ] {
+            assert!(parse_sort(value, FIELDS).is_none(), "{value}");
+        }
+    }
+
+    #[test]
+    fn last_page_rounds_up_and_is_at_least_one() {
+        assert_eq!(last_page(0, 15), 1);
+        assert_eq!(last_page(1, 15), 1);
+        assert_eq!(last_page(15, 15), 1);
+        assert_eq!(last_page(16, 15), 2);
+        assert_eq!(last_page(100, 10), 10);
+    }
+
+    #[test]
+    fn last_page_handles_invalid_input() {
+        assert_eq!(last_page(-5, 15), 1);
+        assert_eq!(last_page(3, 0), 3);
+        assert_eq!(last_page(0, 0), 1);
+    }
+
+    #[test]
+    fn like_patterns_escape_wildcards() {
+        assert_eq!(escape_like("50%_off\\"), "50\\%\\_off\\\\");
+        assert_eq!(like_contains("a_b"), "%a\\_b%");
+    }
+}
@@ -0,0 +1,21 @@
+use bcrypt::{BcryptError, hash, verify};
+use std::time::Instant;
+
+use crate::utils::metrics::record_bcrypt;
+
+// cost dari auth.bcrypt_cost, hash lama tetap bisa diverifikasi karena cost ikut tersimpan di hash
+pub fn hash_password(password: &str, cost: u32) -> Result<String, BcryptError> {
+    let started = Instant::now();
+    let hashed = hash(password, cost);
+    record_bcrypt("hash", started);
+
+    hashed
+}
+
+pub fn verify_password(password: &str, hashed: &str) -> Result<bool, BcryptError> {
+    let started = Instant::now();
+    let valid = verify(password, hashed);
+    record_bcrypt("verify", started);
+
+    valid
+}
@@ -0,0 +1,36 @@
+use chrono::{Duration, Utc};
+use sqlx::{Executor, MySql};
+
+use crate::utils::signed_token;
+
+// purpose yang ikut ditandatangani di token reset password
+pub const PASSWORD_RESET_PURPOSE: &str = "password-reset";
+
+// masa berlaku link reset password
+pub const PASSWORD_RESET_TTL_MINUTES: i64 = 60;
+
+// simpan hash token reset password baru, kembalikan token plaintext untuk dikirim via email
+pub async fn issue_password_reset_token<'e, E>(
+    executor: E,
+    user_id: i64,
+) -> Result<String, sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    let (token, token_hash) = signed_token::generate(PASSWORD_RESET_PURPOSE);
+    let expires_at = Utc::now() + Duration::minutes(PASSWORD_RESET_TTL_MINUTES);
+
+    sqlx::query!(
+        r#"
+        INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
+        VALUES (?, ?, ?)
+        "#,
+        user_id,
+        token_hash,
+        expires_at
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(token)
+}
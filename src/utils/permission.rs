@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+use sqlx::{Executor, MySql, MySqlPool};
+
+use crate::i18n::locale::Locale;
+
+// nama permission yang dikenal aplikasi (lihat tabel permissions)
+pub const USERS_READ: &str = "users.read";
+pub const USERS_WRITE: &str = "users.write";
+pub const USERS_DELETE: &str = "users.delete";
+pub const USERS_PURGE: &str = "users.purge";
+pub const ROLES_MANAGE: &str = "roles.manage";
+pub const AUDIT_READ: &str = "audit.read";
+
+// permission efektif milik user, dihitung sekali per request oleh middleware auth
+#[derive(Clone, Debug, Default)]
+pub struct PermissionSet(HashSet<String>);
+
+impl PermissionSet {
+    pub fn has(&self, permission: &str) -> bool {
+        self.0.contains(permission)
+    }
+
+    // user tanpa permission sama sekali (role member)
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // daftar permission terurut, dikirim ke frontend lewat /api/me
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.0.iter().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+// gabungan permission dari semua role milik user
+pub async fn load_permissions(db: &MySqlPool, user_id: i64) -> Result<PermissionSet, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT DISTINCT p.name
+        FROM user_roles ur
+        JOIN role_permissions rp ON rp.role_id = ur.role_id
+        JOIN permissions p ON p.id = rp.permission_id
+        WHERE ur.user_id = ?
+        "#,
+        user_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(PermissionSet(rows.into_iter().map(|row| row.name).collect()))
+}
+
+// permission & bahasa pilihan user dalam satu query, dipakai middleware auth di setiap request
+// user yang sudah dihapus tidak menghasilkan baris sehingga permission-nya kosong
+pub async fn load_permissions_and_locale(
+    db: &MySqlPool,
+    user_id: i64,
+) -> Result<(PermissionSet, Option<Locale>), sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT DISTINCT u.locale, p.name AS `name?`
+        FROM users u
+        LEFT JOIN user_roles ur ON ur.user_id = u.id
+        LEFT JOIN role_permissions rp ON rp.role_id = ur.role_id
+        LEFT JOIN permissions p ON p.id = rp.permission_id
+        WHERE u.id = ? AND u.deleted_at IS NULL
+        "#,
+        user_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    let locale = rows
+        .first()
+        .and_then(|row| row.locale.as_deref())
+        .and_then(Locale::parse);
+
+    let permissions = PermissionSet(rows.into_iter().filter_map(|row| row.name).collect());
+
+    Ok((permissions, locale))
+}
+
+// nama role milik user, dikirim ke frontend saat login
+pub async fn load_role_names<'e, E>(executor: E, user_id: i64) -> Result<Vec<String>, sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    let rows = sqlx::query!(
+        r#"
+        SELECT r.name
+        FROM user_roles ur
+        JOIN roles r ON r.id = ur.role_id
+        WHERE ur.user_id = ?
+        ORDER BY r.name
+        "#,
+        user_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.name).collect())
+}
+
+// role bawaan untuk user baru
+pub const DEFAULT_ROLE: &str = "member";
+
+// role bawaan pemegang seluruh permission admin
+pub const ADMIN_ROLE: &str = "admin";
+
+// role bawaan tidak boleh diganti nama atau dihapus lewat API
+pub fn is_built_in_role(name: &str) -> bool {
+    name == DEFAULT_ROLE || name == ADMIN_ROLE
+}
+
+pub async fn assign_default_role<'e, E>(executor: E, user_id: i64) -> Result<(), sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    sqlx::query!(
+        r#"
+        INSERT IGNORE INTO user_roles (user_id, role_id)
+        SELECT ?, id FROM roles WHERE name = ?
+        "#,
+        user_id,
+        DEFAULT_ROLE
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header::CONTENT_TYPE},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+// format body error: envelope {status, message, data} atau problem details RFC 7807
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorFormat {
+    Envelope,
+    Problem,
+}
+
+impl ErrorFormat {
+    // problem+json dipakai bila diminta lewat header Accept, selain itu ikut default dari config
+    pub fn negotiate(accept: Option<&str>, default: Self) -> Self {
+        let requested = accept.is_some_and(|accept| {
+            accept.split(',').any(|entry| {
+                let mut parts = entry.split(';');
+                let media_type = parts.next().unwrap_or_default().trim();
+
+                // q=0 berarti client menolak tipe ini
+                let rejected = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .any(|q| q.trim().parse::<f32>().is_ok_and(|q| q <= 0.0));
+
+                media_type.eq_ignore_ascii_case(PROBLEM_JSON) && !rejected
+            })
+        });
+
+        if requested { Self::Problem } else { default }
+    }
+}
+
+// pengaturan problem details dari config
+pub struct ProblemOptions {
+    pub default_format: ErrorFormat,
+    // prefix URI untuk field "type", None berarti "about:blank"
+    pub type_base_url: Option<String>,
+}
+
+// data request yang dibutuhkan untuk merender problem details
+#[derive(Clone)]
+pub struct ProblemContext {
+    pub instance: String,
+    pub options: Arc<ProblemOptions>,
+}
+
+tokio::task_local! {
+    static CURRENT: ProblemContext;
+}
+
+// jalankan request dalam mode problem+json, dipakai middleware problem
+pub async fn scope<F: std::future::Future>(context: ProblemContext, future: F) -> F::Output {
+    CURRENT.scope(context, future).await
+}
+
+// Some bila request yang sedang diproses meminta problem+json
+pub fn current() -> Option<ProblemContext> {
+    CURRENT.try_with(|context| context.clone()).ok()
+}
+
+// problem details RFC 7807, code & errors adalah extension member
+#[derive(Serialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub instance: String,
+    pub code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Value>,
+}
+
+impl ProblemDetails {
+    pub fn new(
+        context: &ProblemContext,
+        status: StatusCode,
+        code: &'static str,
+        detail: String,
+        errors: Option<Value>,
+    ) -> Self {
+        // auth.token_revoked → <base>/auth/token-revoked
+        let problem_type = match &context.options.type_base_url {
+            Some(base) => format!(
+                "{}/{}",
+                base.trim_end_matches('/'),
+                code.replace('.', "/").replace('_', "-")
+            ),
+            None => "about:blank".to_string(),
+        };
+
+        Self {
+            problem_type,
+            title: status.canonical_reason().unwrap_or_default().to_string(),
+            status: status.as_u16(),
+            detail,
+            instance: context.instance.clone(),
+            code,
+            errors,
+        }
+    }
+}
+
+impl IntoResponse for ProblemDetails {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = (status, Json(self)).into_response();
+
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ErrorFormat::{Envelope, Problem};
+
+    #[test]
+    fn negotiate_picks_problem_json_when_requested() {
+        assert_eq!(
+            ErrorFormat::negotiate(Some(PROBLEM_JSON), Envelope),
+            Problem
+        );
+        assert_eq!(
+            ErrorFormat::negotiate(
+                Some("application/json, application/problem+json;q=0.5"),
+                Envelope
+            ),
+            Problem
+        );
+        assert_eq!(
+            ErrorFormat::negotiate(Some(" Application/Problem+JSON ; charset=utf-8"), Envelope),
+            Problem
+        );
+    }
+
+    #[test]
+    fn negotiate_falls_back_to_default() {
+        for accept in [
+            None,
+            Some("*/*"),
+            Some("application/*"),
+            Some("application/json"),
+            Some(""),
+        ] {
+            assert_eq!(
+                ErrorFormat::negotiate(accept, Envelope),
+                Envelope,
+                "{accept:?}"
+            );
+            assert_eq!(
+                ErrorFormat::negotiate(accept, Problem),
+                Problem,
+                "{accept:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn negotiate_respects_q_zero() {
+        assert_eq!(
+            ErrorFormat::negotiate(Some("application/problem+json;q=0"), Envelope),
+            Envelope
+        );
+        assert_eq!(
+            ErrorFormat::negotiate(Some("application/problem+json; q=0.0, */*"), Envelope),
+            Envelope
+        );
+        assert_eq!(
+            ErrorFormat::negotiate(Some("application/problem+json;q=0.1"), Envelope),
+            Problem
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+// jumlah bucket sebelum bucket yang sudah penuh kembali dibersihkan
+const PRUNE_THRESHOLD: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+// hasil pengecekan rate limit, dikirim lewat header RateLimit-*
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    // detik sampai bucket penuh kembali
+    pub reset: u64,
+    // detik sampai request berikutnya diizinkan (hanya bila ditolak)
+    pub retry_after: u64,
+}
+
+// token bucket: `capacity` request, terisi penuh kembali dalam `window`
+pub struct RateLimiter {
+    capacity: u32,
+    refill_per_second: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(capacity: u32, window: Duration) -> Self {
+        Self {
+            capacity,
+            refill_per_second: capacity as f64 / window.as_secs_f64().max(1.0),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) {
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens =
+            (bucket.tokens + elapsed * self.refill_per_second).min(self.capacity as f64);
+        bucket.updated_at = now;
+    }
+
+    // ambil satu token dari bucket milik key
+    pub fn check(&self, key: &str) -> RateLimitDecision {
+        self.check_at(key, Instant::now())
+    }
+
+    // waktu dikirim dari luar supaya perhitungan refill bisa diuji
+    fn check_at(&self, key: &str, now: Instant) -> RateLimitDecision {
+        let capacity = self.capacity as f64;
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| {
+                let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+                bucket.tokens + elapsed * self.refill_per_second < capacity
+            });
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+
+        self.refill(bucket, now);
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        let retry_after = if allowed {
+            0
+        } else {
+            ((1.0 - bucket.tokens) / self.refill_per_second).ceil() as u64
+        };
+
+        RateLimitDecision {
+            allowed,
+            limit: self.capacity,
+            remaining: bucket.tokens.floor() as u32,
+            reset: ((capacity - bucket.tokens) / self.refill_per_second).ceil() as u64,
+            retry_after,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "ip:127.0.0.1";
+
+    // 3 request per 60 detik, terisi 1 token setiap 20 detik
+    fn limiter() -> RateLimiter {
+        RateLimiter::new(3, Duration::from_secs(60))
+    }
+
+    #[test]
+    fn allows_burst_up_to_capacity() {
+        let limiter = limiter();
+        let now = Instant::now();
+
+        for remaining in [2, 1, 0] {
+            let decision = limiter.check_at(KEY, now);
+            assert!(decision.allowed);
+            assert_eq!(decision.limit, 3);
+            assert_eq!(decision.remaining, remaining);
+            assert_eq!(decision.retry_after, 0);
+        }
+
+        let decision = limiter.check_at(KEY, now);
+        assert!(!decision.allowed);
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(decision.retry_after, 20);
+        assert_eq!(decision.reset, 60);
+    }
+
+    #[test]
+    fn refills_one_token_per_interval() {
+        let limiter = limiter();
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.check_at(KEY, start);
+        }
+
+        // belum satu token penuh
+        let decision = limiter.check_at(KEY, start + Duration::from_secs(10));
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after, 10);
+
+        let decision = limiter.check_at(KEY, start + Duration::from_secs(20));
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+
+        let decision = limiter.check_at(KEY, start + Duration::from_secs(21));
+        assert!(!decision.allowed);
+    }
+
+    #[test]
+    fn refill_is_capped_at_capacity() {
+        let limiter = limiter();
+        let start = Instant::now();
+
+        limiter.check_at(KEY, start);
+
+        // jeda panjang tidak menambah token melebihi kapasitas
+        let later = start + Duration::from_secs(3600);
+        let decision = limiter.check_at(KEY, later);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 2);
+        assert_eq!(decision.reset, 20);
+
+        limiter.check_at(KEY, later);
+        limiter.check_at(KEY, later);
+        assert!(!limiter.check_at(KEY, later).allowed);
+    }
+
+    #[test]
+    fn keys_have_separate_buckets() {
+        let limiter = limiter();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            limiter.check_at(KEY, now);
+        }
+
+        assert!(!limiter.check_at(KEY, now).allowed);
+        assert!(limiter.check_at("user:1", now).allowed);
+    }
+
+    #[test]
+    fn window_shorter_than_one_second_is_treated_as_one_second() {
+        let limiter = RateLimiter::new(2, Duration::from_millis(100));
+        let now = Instant::now();
+
+        limiter.check_at(KEY, now);
+        limiter.check_at(KEY, now);
+
+        let decision = limiter.check_at(KEY, now);
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after, 1);
+    }
+}
@@ -0,0 +1,54 @@
+use chrono::{Duration, Utc};
+use rand::{RngCore, rngs::OsRng};
+use sha2::{Digest, Sha256};
+use sqlx::{Executor, MySql};
+
+// generate string acak (hex) dari sejumlah byte
+pub fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+// family baru dibuat setiap kali user login
+pub fn new_family_id() -> String {
+    random_hex(16)
+}
+
+// hanya hash SHA-256 dari refresh token yang disimpan di database
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+// simpan refresh token baru yang berlaku ttl_days hari, kembalikan (id, token plaintext)
+// mfa_verified: sesi dimulai lewat login 2FA
+pub async fn issue_refresh_token<'e, E>(
+    executor: E,
+    user_id: i64,
+    family_id: &str,
+    mfa_verified: bool,
+    ttl_days: i64,
+) -> Result<(i64, String), sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    let token = random_hex(32);
+    let token_hash = hash_token(&token);
+    let expires_at = Utc::now() + Duration::days(ttl_days);
+
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO refresh_tokens (user_id, family_id, mfa_verified, token_hash, expires_at)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+        user_id,
+        family_id,
+        mfa_verified,
+        token_hash,
+        expires_at
+    )
+    .execute(executor)
+    .await?;
+
+    Ok((result.last_insert_id() as i64, token))
+}
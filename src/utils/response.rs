@@ -1,28 +1,51 @@
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+use crate::utils::pagination::PaginationMeta;
+
+#[derive(Serialize, ToSchema)]
 
 pub struct ApiResponse<T> {
     pub status: bool,
+    // kode error yang stabil untuk dibaca mesin, hanya ada di response error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub code: Option<&'static str>,
     pub message: String, 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<PaginationMeta>,
 }
 
 impl<T> ApiResponse<T> {
     pub fn success(message: &str, data: T) -> Self {
         Self {
             status: true, 
+            code: None,
+            message: message.to_string(),
+            data: Some(data),
+            meta: None,
+        }
+    }
+
+    pub fn paginated(message: &str, data: T, meta: PaginationMeta) -> Self {
+        Self {
+            status: true,
+            code: None,
             message: message.to_string(),
-            data: Some(data)
+            data: Some(data),
+            meta: Some(meta),
         }
     }
 
     pub fn error(message: &str) -> Self {
         Self {
             status: false, 
+            code: None,
             message: message.to_string(),
-            data: None
+            data: None,
+            meta: None,
         }
     }
 }
@@ -0,0 +1,162 @@
+use std::{
+    collections::HashMap,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+
+use crate::utils::jwt::{Claims, MfaClaims};
+
+// berapa lama status "belum dicabut" dipercaya sebelum dicek ulang ke database
+const NOT_REVOKED_TTL: Duration = Duration::from_secs(30);
+
+// jumlah entry sebelum token yang sudah kedaluwarsa dibersihkan dari cache
+const PRUNE_THRESHOLD: usize = 10_000;
+
+struct CacheEntry {
+    user_id: i64,
+    exp: usize,
+    revoked: bool,
+    checked_at: Instant,
+}
+
+// cache status pencabutan token per jti, di depan tabel revoked_tokens
+#[derive(Default)]
+pub struct RevocationCache {
+    entries: RwLock<HashMap<String, CacheEntry>>,
+}
+
+impl RevocationCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn cached(&self, jti: &str) -> Option<bool> {
+        let entries = self.entries.read().unwrap();
+        let entry = entries.get(jti)?;
+
+        // token yang sudah dicabut tidak akan aktif lagi
+        if entry.revoked || entry.checked_at.elapsed() < NOT_REVOKED_TTL {
+            Some(entry.revoked)
+        } else {
+            None
+        }
+    }
+
+    fn remember(&self, claims: &Claims, revoked: bool) {
+        let mut entries = self.entries.write().unwrap();
+
+        if entries.len() >= PRUNE_THRESHOLD {
+            let now = Utc::now().timestamp() as usize;
+            entries.retain(|_, entry| entry.exp > now);
+        }
+
+        entries.insert(
+            claims.jti.clone(),
+            CacheEntry {
+                user_id: claims.sub,
+                exp: claims.exp,
+                revoked,
+                checked_at: Instant::now(),
+            },
+        );
+    }
+
+    // cek apakah token sudah dicabut, database hanya dibaca saat cache kosong/basi
+    // iat & tokens_revoked_before sama-sama presisi detik, token yang terbit di detik yang sama
+    // dengan pencabutan (mis. login ulang tepat setelah ganti password) tetap berlaku
+    pub async fn is_revoked(&self, db: &MySqlPool, claims: &Claims) -> Result<bool, sqlx::Error> {
+        if let Some(revoked) = self.cached(&claims.jti) {
+            return Ok(revoked);
+        }
+
+        let row = sqlx::query!(
+            r#"
+            SELECT (
+                EXISTS(SELECT 1 FROM revoked_tokens WHERE jti = ?)
+                OR EXISTS(
+                    SELECT 1 FROM users
+                    WHERE id = ? AND tokens_revoked_before > FROM_UNIXTIME(?)
+                )
+            ) AS `revoked!: bool`
+            "#,
+            claims.jti,
+            claims.sub,
+            claims.iat as i64
+        )
+        .fetch_one(db)
+        .await?;
+
+        self.remember(claims, row.revoked);
+
+        Ok(row.revoked)
+    }
+
+    // simpan jti ke tabel revoked_tokens dan langsung tandai di cache
+    pub async fn revoke(&self, db: &MySqlPool, claims: &Claims) -> Result<(), sqlx::Error> {
+        let expires_at = DateTime::<Utc>::from_timestamp(claims.exp as i64, 0)
+            .unwrap_or_else(Utc::now);
+
+        sqlx::query!(
+            r#"
+            INSERT IGNORE INTO revoked_tokens (jti, user_id, expires_at)
+            VALUES (?, ?, ?)
+            "#,
+            claims.jti,
+            claims.sub,
+            expires_at
+        )
+        .execute(db)
+        .await?;
+
+        // bersihkan jti yang token-nya sudah kedaluwarsa
+        sqlx::query!("DELETE FROM revoked_tokens WHERE expires_at < NOW()")
+            .execute(db)
+            .await?;
+
+        self.remember(claims, true);
+
+        Ok(())
+    }
+
+    // logout dari semua perangkat: status cache milik user ini dicek ulang ke database
+    pub fn forget_user(&self, user_id: i64) {
+        self.entries
+            .write()
+            .unwrap()
+            .retain(|_, entry| entry.user_id != user_id);
+    }
+}
+
+// token "mfa pending" sudah dipakai login atau dibakar karena terlalu sering salah
+pub async fn is_mfa_token_used(db: &MySqlPool, claims: &MfaClaims) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query!(
+        "SELECT EXISTS(SELECT 1 FROM revoked_tokens WHERE jti = ?) AS `used!: bool`",
+        claims.jti
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(row.used)
+}
+
+// tandai token "mfa pending" sudah dipakai, false bila request lain sudah lebih dulu memakainya
+pub async fn consume_mfa_token(db: &MySqlPool, claims: &MfaClaims) -> Result<bool, sqlx::Error> {
+    let expires_at = DateTime::<Utc>::from_timestamp(claims.exp as i64, 0)
+        .unwrap_or_else(Utc::now);
+
+    let result = sqlx::query!(
+        r#"
+        INSERT IGNORE INTO revoked_tokens (jti, user_id, expires_at)
+        VALUES (?, ?, ?)
+        "#,
+        claims.jti,
+        claims.sub,
+        expires_at
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
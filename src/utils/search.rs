@@ -0,0 +1,171 @@
+use crate::utils::pagination::escape_like;
+
+// panjang minimum kata yang diindeks FULLTEXT InnoDB (innodb_ft_min_token_size)
+pub const MIN_FULLTEXT_TERM_LENGTH: usize = 3;
+
+// pecah query menjadi kata, karakter operator boolean MySQL dibuang
+fn terms(query: &str) -> impl Iterator<Item = &str> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+}
+
+// kata yang cukup panjang untuk dicari lewat FULLTEXT
+pub fn fulltext_terms(query: &str) -> Vec<String> {
+    terms(query)
+        .filter(|term| term.chars().count() >= MIN_FULLTEXT_TERM_LENGTH)
+        .map(|term| term.to_string())
+        .collect()
+}
+
+// kata yang tidak diindeks FULLTEXT, dicari dengan LIKE supaya "jo smith" tetap memfilter "jo"
+pub fn short_terms(query: &str) -> Vec<String> {
+    terms(query)
+        .filter(|term| term.chars().count() < MIN_FULLTEXT_TERM_LENGTH)
+        .map(|term| term.to_string())
+        .collect()
+}
+
+// "john smi" → "+john* +smi*" (BOOLEAN MODE, semua kata wajib, cocok sebagai prefix)
+pub fn boolean_query(terms: &[String]) -> String {
+    terms
+        .iter()
+        .map(|term| format!("+{}*", term))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// pola LIKE "diawali"
+pub fn like_prefix(value: &str) -> String {
+    format!("{}%", escape_like(value))
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// posisi byte (awal, akhir) kemunculan pertama needle, tanpa membedakan huruf besar/kecil
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<(usize, usize)> {
+    let hay = haystack.char_indices().collect::<Vec<(usize, char)>>();
+    let needle = needle.chars().collect::<Vec<char>>();
+
+    if needle.is_empty() || hay.len() < needle.len() {
+        return None;
+    }
+
+    (0..=hay.len() - needle.len()).find_map(|i| {
+        let matched = needle
+            .iter()
+            .enumerate()
+            .all(|(k, c)| hay[i + k].1.to_lowercase().eq(c.to_lowercase()));
+
+        matched.then(|| {
+            let start = hay[i].0;
+            let end = hay
+                .get(i + needle.len())
+                .map(|(index, _)| *index)
+                .unwrap_or(haystack.len());
+
+            (start, end)
+        })
+    })
+}
+
+// tandai bagian text yang cocok dengan salah satu kata dengan <mark>, sisanya di-escape
+pub fn highlight(text: &str, terms: &[String]) -> Option<String> {
+    let (start, end) = terms
+        .iter()
+        .find_map(|term| find_ignore_case(text, term))?;
+
+    Some(format!(
+        "{}<mark>{}</mark>{}",
+        escape_html(&text[..start]),
+        escape_html(&text[start..end]),
+        escape_html(&text[end..]),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn fulltext_terms_strip_boolean_operators() {
+        assert_eq!(
+            fulltext_terms(r#"+john -smith* "doe" (ann) ~bob> <eve @2"#),
+            strings(&["john", "smith", "doe", "ann", "bob", "eve"])
+        );
+        assert!(fulltext_terms("+-*~<>()\"@").is_empty());
+    }
+
+    #[test]
+    fn short_terms_are_split_from_fulltext_terms() {
+        assert_eq!(fulltext_terms("jo smith"), strings(&["smith"]));
+        assert_eq!(short_terms("jo smith"), strings(&["jo"]));
+        assert!(short_terms("john smith").is_empty());
+        assert!(fulltext_terms("jo li").is_empty());
+    }
+
+    #[test]
+    fn term_length_counts_characters_not_bytes() {
+        // "Bö" 3 byte tapi 2 karakter, "Zoë" 4 byte dan 3 karakter
+        assert_eq!(short_terms("Bö Zoë"), strings(&["Bö"]));
+        assert_eq!(fulltext_terms("Bö Zoë"), strings(&["Zoë"]));
+    }
+
+    #[test]
+    fn boolean_query_requires_every_term_as_prefix() {
+        assert_eq!(boolean_query(&strings(&["john", "smi"])), "+john* +smi*");
+        assert_eq!(boolean_query(&[]), "");
+    }
+
+    #[test]
+    fn like_prefix_escapes_wildcards() {
+        assert_eq!(like_prefix("jo_"), "jo\\_%");
+        assert_eq!(like_prefix("100%"), "100\\%%");
+    }
+
+    #[test]
+    fn highlight_marks_first_match_ignoring_case() {
+        assert_eq!(
+            highlight("John Smith", &strings(&["smith"])).as_deref(),
+            Some("John <mark>Smith</mark>")
+        );
+        assert_eq!(highlight("John Smith", &strings(&["doe"])), None);
+        assert_eq!(highlight("John Smith", &[]), None);
+        assert_eq!(highlight("Jo", &strings(&["john"])), None);
+    }
+
+    #[test]
+    fn highlight_escapes_html() {
+        assert_eq!(
+            highlight("<b>Tom & \"Jerry\"</b>", &strings(&["jerry"])).as_deref(),
+            Some("&lt;b&gt;Tom &amp; &quot;<mark>Jerry</mark>&quot;&lt;/b&gt;")
+        );
+    }
+
+    #[test]
+    fn highlight_slices_on_character_boundaries() {
+        assert_eq!(
+            highlight("José Ñúñez", &strings(&["ñúñ"])).as_deref(),
+            Some("José <mark>Ñúñ</mark>ez")
+        );
+        assert_eq!(
+            highlight("日本語テキスト", &strings(&["テキ"])).as_deref(),
+            Some("日本語<mark>テキ</mark>スト")
+        );
+        assert_eq!(
+            highlight("André", &strings(&["é"])).as_deref(),
+            Some("Andr<mark>é</mark>")
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use std::{future::IntoFuture, io, time::Duration};
+use tokio::sync::watch;
+
+// menunggu SIGINT (Ctrl+C) atau SIGTERM (docker stop, kubernetes)
+async fn signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!(error = %e, "Failed to Listen for Ctrl+C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to Listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+// status shutdown yang dibagikan ke server, true setelah sinyal diterima
+#[derive(Clone)]
+pub struct Shutdown(watch::Receiver<bool>);
+
+impl Shutdown {
+    // mulai mendengarkan sinyal OS, dipanggil sekali saat startup
+    pub fn listen() -> Self {
+        let (sender, receiver) = watch::channel(false);
+
+        tokio::spawn(async move {
+            signal().await;
+            tracing::info!("Shutdown signal received, draining connections");
+            let _ = sender.send(true);
+        });
+
+        Self(receiver)
+    }
+
+    // selesai saat sinyal shutdown diterima, dipakai axum untuk berhenti menerima koneksi baru
+    pub async fn triggered(mut self) {
+        let _ = self.0.wait_for(|triggered| *triggered).await;
+    }
+
+    // jalankan server sampai semua request selesai, koneksi yang tersisa diputus setelah drain timeout
+    pub async fn drain<F>(self, server: F, timeout: Duration) -> io::Result<()>
+    where
+        F: IntoFuture<Output = io::Result<()>>,
+    {
+        let deadline = async {
+            self.triggered().await;
+            tokio::time::sleep(timeout).await;
+        };
+
+        tokio::select! {
+            result = server.into_future() => result,
+            _ = deadline => {
+                tracing::warn!(
+                    timeout_seconds = timeout.as_secs(),
+                    "Drain timeout reached, closing remaining connections"
+                );
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::OnceLock;
+
+use crate::utils::refresh_token::{hash_token, random_hex};
+
+type HmacSha256 = Hmac<Sha256>;
+
+static SECRET: OnceLock<Vec<u8>> = OnceLock::new();
+
+// dipanggil sekali saat startup dari main
+pub fn init(secret: Vec<u8>) {
+    if SECRET.set(secret).is_err() {
+        panic!("APP_KEY sudah di-load");
+    }
+}
+
+// kunci HMAC untuk token internal, tidak ikut dirotasi bersama key JWT
+pub fn secret() -> &'static [u8] {
+    SECRET.get().expect("APP_KEY belum di-load")
+}
+
+// purpose ikut ditandatangani supaya token verifikasi email tidak bisa dipakai untuk keperluan lain
+fn mac(purpose: &str, value: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret()).expect("HMAC can take key of any size");
+    mac.update(purpose.as_bytes());
+    mac.update(b".");
+    mac.update(value.as_bytes());
+    mac
+}
+
+// generate token "<acak>.<signature>", kembalikan (token plaintext, hash untuk database)
+pub fn generate(purpose: &str) -> (String, String) {
+    let value = random_hex(32);
+    let signature = hex::encode(mac(purpose, &value).finalize().into_bytes());
+
+    (format!("{}.{}", value, signature), hash_token(&value))
+}
+
+// cek signature token, kembalikan hash untuk dicari di database
+pub fn verify(purpose: &str, token: &str) -> Option<String> {
+    let (value, signature) = token.split_once('.')?;
+    let signature = hex::decode(signature).ok()?;
+
+    mac(purpose, value).verify_slice(&signature).ok()?;
+
+    Some(hash_token(value))
+}
@@ -0,0 +1,226 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::{RngCore, rngs::OsRng};
+use sha1::Sha1;
+
+use crate::utils::refresh_token::random_hex;
+
+type HmacSha1 = Hmac<Sha1>;
+
+// parameter TOTP (RFC 6238) yang didukung semua aplikasi authenticator
+const DIGITS: u32 = 6;
+const PERIOD_SECONDS: i64 = 30;
+
+// toleransi selisih jam client: 1 step sebelum & sesudah
+const ALLOWED_SKEW_STEPS: i64 = 1;
+
+// jumlah recovery code yang dibuat sekali setup
+pub const RECOVERY_CODE_COUNT: usize = 8;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut output = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            output.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        output.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    output
+}
+
+fn base32_decode(value: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in value.trim_end_matches('=').chars() {
+        let index = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a as char == c.to_ascii_uppercase())?;
+
+        buffer = (buffer << 5) | index as u32;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(output)
+}
+
+// percent-encoding untuk label & parameter otpauth URI
+fn encode_uri_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn hotp(key: &[u8], counter: u64) -> u32 {
+    let mut mac = HmacSha1::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    // dynamic truncation (RFC 4226)
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let code = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+
+    code % 10u32.pow(DIGITS)
+}
+
+// secret baru (160 bit) dalam base32, disimpan di users.two_factor_secret
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 20];
+    OsRng.fill_bytes(&mut bytes);
+    base32_encode(&bytes)
+}
+
+// URI yang di-scan aplikasi authenticator (QR code dibuat di frontend)
+pub fn otpauth_uri(issuer: &str, account: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        encode_uri_component(issuer),
+        encode_uri_component(account),
+        secret,
+        encode_uri_component(issuer),
+        DIGITS,
+        PERIOD_SECONDS
+    )
+}
+
+// cek kode TOTP, kembalikan step yang cocok supaya kode yang sama tidak bisa dipakai ulang
+pub fn verify(secret: &str, code: &str, last_used_step: Option<i64>) -> Option<i64> {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let code: u32 = code.parse().ok()?;
+    let key = base32_decode(secret)?;
+    let current = Utc::now().timestamp() / PERIOD_SECONDS;
+
+    (current - ALLOWED_SKEW_STEPS..=current + ALLOWED_SKEW_STEPS)
+        .filter(|step| last_used_step.is_none_or(|last| *step > last))
+        .find(|step| hotp(&key, *step as u64) == code)
+}
+
+// recovery code sekali pakai, format "xxxxx-xxxxx"
+pub fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let value = random_hex(5);
+            format!("{}-{}", &value[..5], &value[5..])
+        })
+        .collect()
+}
+
+// recovery code dibandingkan tanpa memperhatikan huruf besar & spasi
+pub fn normalize_recovery_code(code: &str) -> String {
+    code.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // secret uji dari RFC 4226 Appendix D & RFC 6238 Appendix B (SHA1)
+    const RFC_KEY: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn hotp_matches_rfc4226_vectors() {
+        let expected = [
+            755224, 287082, 359152, 969429, 338314, 254676, 287922, 162583, 399871, 520489,
+        ];
+
+        for (counter, code) in expected.into_iter().enumerate() {
+            assert_eq!(hotp(RFC_KEY, counter as u64), code, "counter {counter}");
+        }
+    }
+
+    #[test]
+    fn totp_matches_rfc6238_vectors() {
+        // RFC 6238 memakai 8 digit, kode 6 digit adalah 6 digit terakhirnya
+        let vectors = [
+            (59, "94287082"),
+            (1111111109, "07081804"),
+            (1111111111, "14050471"),
+            (1234567890, "89005924"),
+            (2000000000, "69279037"),
+            (20000000000, "65353130"),
+        ];
+
+        for (time, code) in vectors {
+            let step = (time / PERIOD_SECONDS) as u64;
+            let expected: u32 = code[2..].parse().unwrap();
+            assert_eq!(hotp(RFC_KEY, step), expected, "time {time}");
+        }
+    }
+
+    #[test]
+    fn base32_matches_rfc4648_vectors() {
+        let vectors = [
+            ("", ""),
+            ("f", "MY"),
+            ("fo", "MZXQ"),
+            ("foo", "MZXW6"),
+            ("foob", "MZXW6YQ"),
+            ("fooba", "MZXW6YTB"),
+            ("foobar", "MZXW6YTBOI"),
+        ];
+
+        for (plain, encoded) in vectors {
+            assert_eq!(base32_encode(plain.as_bytes()), encoded);
+            assert_eq!(base32_decode(encoded).unwrap(), plain.as_bytes());
+        }
+    }
+
+    #[test]
+    fn base32_round_trips_secret() {
+        let secret = generate_secret();
+        assert_eq!(secret.len(), 32);
+
+        let key = base32_decode(&secret).unwrap();
+        assert_eq!(key.len(), 20);
+        assert_eq!(base32_encode(&key), secret);
+
+        // padding & huruf kecil tetap diterima
+        assert_eq!(base32_decode("mzxw6ytboi======").unwrap(), b"foobar");
+        assert!(base32_decode("MZXW1").is_none());
+    }
+
+    #[test]
+    fn verify_rejects_reused_step() {
+        let secret = base32_encode(RFC_KEY);
+        let step = Utc::now().timestamp() / PERIOD_SECONDS;
+        let code = format!("{:06}", hotp(RFC_KEY, step as u64));
+
+        let used = verify(&secret, &code, None).expect("current code accepted");
+        assert!(verify(&secret, &code, Some(used)).is_none());
+        assert!(verify(&secret, "12345", None).is_none());
+    }
+}
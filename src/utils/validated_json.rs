@@ -0,0 +1,29 @@
+use axum::{
+    Json,
+    extract::{FromRequest, Request, rejection::JsonRejection},
+};
+use serde::de::DeserializeOwned;
+use validator::Validate;
+
+use crate::utils::app_error::AppError;
+
+// body JSON yang sudah divalidasi, error parsing & validasi langsung jadi AppError
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(|_: JsonRejection| AppError::InvalidBody("error.invalid_body"))?;
+
+        value.validate()?;
+
+        Ok(Self(value))
+    }
+}